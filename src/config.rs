@@ -4,7 +4,18 @@ use serde::Deserialize;
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-#[derive(Debug, Deserialize, Clone)]
+/// Folder keys accepted by `wardex config goto`
+pub const FOLDER_KEYS: &[&str] = &[
+    "workspace",
+    "inbox",
+    "projects",
+    "areas",
+    "resources",
+    "archives",
+    "ctf",
+];
+
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct Config {
     pub paths: Paths,
     #[serde(default)]
@@ -13,17 +24,14 @@ pub struct Config {
     pub organize: Organize,
     #[serde(default)]
     pub ctf: CtfConfig,
-}
-
-impl Default for Config {
-    fn default() -> Self {
-        Self {
-            paths: Paths::default(),
-            rules: Rules::default(),
-            organize: Organize::default(),
-            ctf: CtfConfig::default(),
-        }
-    }
+    #[serde(default)]
+    pub search: SearchConfig,
+    #[serde(default)]
+    pub index: IndexConfig,
+    #[serde(default)]
+    pub status: StatusConfig,
+    #[serde(default)]
+    pub audit: AuditConfig,
 }
 
 /// Explicit path configuration
@@ -103,6 +111,101 @@ impl Default for CtfConfig {
     }
 }
 
+/// Limits for flag search. Sizes are in megabytes; `0` disables a limit.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct SearchConfig {
+    /// Files larger than this are skipped (archives included)
+    pub max_file_size_mb: u64,
+    /// Archive entries larger than this are skipped
+    pub max_archive_entry_size_mb: u64,
+    /// Upper bound on the line buffer used while streaming a file or entry.
+    /// Lines longer than this abort the scan of that file/entry.
+    pub buffer_limit_mb: u64,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            max_file_size_mb: 100,
+            max_archive_entry_size_mb: 50,
+            buffer_limit_mb: 8,
+        }
+    }
+}
+
+fn mb_limit(mb: u64) -> Option<u64> {
+    (mb > 0).then(|| mb.saturating_mul(1024 * 1024))
+}
+
+impl SearchConfig {
+    pub fn max_file_size(&self) -> Option<u64> {
+        mb_limit(self.max_file_size_mb)
+    }
+
+    pub fn max_archive_entry_size(&self) -> Option<u64> {
+        mb_limit(self.max_archive_entry_size_mb)
+    }
+
+    pub fn buffer_limit(&self) -> Option<usize> {
+        mb_limit(self.buffer_limit_mb).map(|b| usize::try_from(b).unwrap_or(usize::MAX))
+    }
+}
+
+/// Content index used by `wardex grep`
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct IndexConfig {
+    /// Files larger than this are not indexed and always grepped live (MB, 0 = unlimited)
+    pub max_file_size_mb: u64,
+    /// Refresh the index after `wardex watch` moves items out of the inbox
+    pub refresh_on_watch: bool,
+}
+
+impl Default for IndexConfig {
+    fn default() -> Self {
+        Self {
+            max_file_size_mb: 8,
+            refresh_on_watch: false,
+        }
+    }
+}
+
+impl IndexConfig {
+    pub fn max_file_size(&self) -> Option<u64> {
+        mb_limit(self.max_file_size_mb)
+    }
+}
+
+/// Repository discovery for `wardex status` and `wardex git`
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct StatusConfig {
+    /// How many levels below the workspace to look for repositories
+    pub discovery_depth: usize,
+    /// Also report submodules, listed under their parent repository
+    pub submodules: bool,
+}
+
+impl Default for StatusConfig {
+    fn default() -> Self {
+        Self {
+            discovery_depth: 3,
+            submodules: false,
+        }
+    }
+}
+
+/// Which checks `wardex audit` runs, and where
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct AuditConfig {
+    /// Check ids to run; empty runs every check
+    pub enabled: Vec<String>,
+    /// Globs (relative to the workspace) of paths never reported
+    pub ignore: Vec<String>,
+}
+
 impl Config {
     /// Load configuration from multiple sources (layered):
     /// 1. Default config file in current directory
@@ -259,6 +362,27 @@ paths:
         );
     }
 
+    #[test]
+    fn test_search_config_defaults_and_unlimited() {
+        let file = create_test_config();
+        let mut config = Config::load_from_file(file.path()).unwrap();
+
+        assert_eq!(config.search.max_file_size(), Some(100 * 1024 * 1024));
+        assert_eq!(
+            config.search.max_archive_entry_size(),
+            Some(50 * 1024 * 1024)
+        );
+
+        config.search.max_archive_entry_size_mb = 0;
+        assert_eq!(config.search.max_archive_entry_size(), None);
+
+        // Huge values saturate instead of overflowing
+        config.search.max_file_size_mb = u64::MAX;
+        config.search.buffer_limit_mb = u64::MAX;
+        assert_eq!(config.search.max_file_size(), Some(u64::MAX));
+        assert_eq!(config.search.buffer_limit(), Some(usize::MAX));
+    }
+
     #[test]
     fn test_ctf_root_helper() {
         let file = create_test_config();
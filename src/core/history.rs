@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Entries kept in the history file; the least frecent are dropped first
+const MAX_ENTRIES: usize = 500;
+
+/// A directory selected through `wardex find`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JumpEntry {
+    pub path: PathBuf,
+    pub visits: u32,
+    /// Unix timestamp (seconds) of the last visit
+    pub last_visit: i64,
+}
+
+impl JumpEntry {
+    /// Visits weighted by recency, in the style of z/zoxide
+    pub fn frecency(&self, now: i64) -> f64 {
+        let age = now - self.last_visit;
+        let weight = if age < 3600 {
+            4.0
+        } else if age < 86400 {
+            2.0
+        } else if age < 7 * 86400 {
+            0.5
+        } else {
+            0.25
+        };
+        self.visits as f64 * weight
+    }
+}
+
+/// History of directories jumped to via wardex, used to rank fuzzy finds
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+pub struct JumpHistory {
+    pub entries: Vec<JumpEntry>,
+}
+
+impl JumpHistory {
+    pub fn load() -> Self {
+        Self::get_history_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        if let Some(path) = Self::get_history_path() {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let content = serde_json::to_string_pretty(self)?;
+            fs::write(path, content)?;
+        }
+        Ok(())
+    }
+
+    /// Record a visit to `path` and persist the history
+    pub fn record(&mut self, path: &Path) -> anyhow::Result<()> {
+        let path = fs::canonicalize(path)?;
+        let now = chrono::Utc::now().timestamp();
+
+        match self.entries.iter_mut().find(|e| e.path == path) {
+            Some(entry) => {
+                entry.visits += 1;
+                entry.last_visit = now;
+            }
+            None => self.entries.push(JumpEntry {
+                path,
+                visits: 1,
+                last_visit: now,
+            }),
+        }
+
+        self.entries.retain(|e| e.path.exists());
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries
+                .sort_by(|a, b| b.frecency(now).total_cmp(&a.frecency(now)));
+            self.entries.truncate(MAX_ENTRIES);
+        }
+        self.save()
+    }
+
+    /// Frecency of `path`, or 0 if it was never visited
+    pub fn frecency(&self, path: &Path) -> f64 {
+        let now = chrono::Utc::now().timestamp();
+        let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        self.entries
+            .iter()
+            .find(|e| e.path == path)
+            .map(|e| e.frecency(now))
+            .unwrap_or(0.0)
+    }
+
+    fn get_history_path() -> Option<PathBuf> {
+        if let Ok(p) = std::env::var("WARDEX_HISTORY_FILE") {
+            return Some(PathBuf::from(p));
+        }
+        dirs::data_dir().map(|d| d.join("wardex").join("history.json"))
+    }
+}
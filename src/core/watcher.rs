@@ -1,12 +1,12 @@
 use crate::config::Config;
-use crate::engine::cleaner;
+use crate::engine::{cleaner, index};
 use anyhow::{Context, Result};
 use log::{debug, error, info, warn};
 use notify_debouncer_mini::{new_debouncer, notify::RecursiveMode};
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
-use std::sync::mpsc::channel;
+use std::sync::mpsc::{channel, Sender};
 use std::time::Duration;
 
 /// Minimum time a file must be stable (unchanged size) before processing
@@ -15,6 +15,10 @@ const FILE_STABILITY_SECONDS: u64 = 2;
 /// Debounce timeout for file system events
 const DEBOUNCE_SECONDS: u64 = 2;
 
+/// Quiet period before a requested index refresh runs, so a burst of
+/// cleanups triggers a single rebuild
+const INDEX_REFRESH_DELAY_SECONDS: u64 = 5;
+
 pub fn watch_inbox(config: &Config) -> Result<()> {
     let inbox_path = config.resolve_path("inbox");
 
@@ -35,6 +39,11 @@ pub fn watch_inbox(config: &Config) -> Result<()> {
         .watcher()
         .watch(&inbox_path, RecursiveMode::NonRecursive)?;
 
+    let index_refresh = config
+        .index
+        .refresh_on_watch
+        .then(|| spawn_index_refresher(config.clone()));
+
     for res in rx {
         match res {
             Ok(events) => {
@@ -50,6 +59,9 @@ pub fn watch_inbox(config: &Config) -> Result<()> {
                         Ok(report) => {
                             if !report.moved.is_empty() {
                                 info!("✓ Auto-cleaned {} items", report.moved.len());
+                                if let Some(refresh) = &index_refresh {
+                                    let _ = refresh.send(());
+                                }
                             }
                             for err in &report.errors {
                                 error!("{}", err);
@@ -68,6 +80,25 @@ pub fn watch_inbox(config: &Config) -> Result<()> {
     Ok(())
 }
 
+/// Refresh the content index on a background thread whenever asked, so
+/// event handling never waits on it. Requests that arrive while waiting or
+/// while a refresh runs are coalesced into the next run.
+fn spawn_index_refresher(config: Config) -> Sender<()> {
+    let (tx, rx) = channel::<()>();
+    std::thread::spawn(move || {
+        while rx.recv().is_ok() {
+            let delay = Duration::from_secs(INDEX_REFRESH_DELAY_SECONDS);
+            while rx.recv_timeout(delay).is_ok() {}
+
+            match index::build_index(&config, false) {
+                Ok(r) => debug!("Content index refreshed ({} files)", r.files),
+                Err(e) => warn!("Index refresh failed: {}", e),
+            }
+        }
+    });
+    tx
+}
+
 /// Wait for all files in the inbox to have stable sizes.
 fn wait_for_stability(inbox_path: &PathBuf) -> bool {
     let max_attempts = 5;
@@ -1,25 +1,100 @@
-use crate::config::Config;
-use anyhow::Result;
+use crate::config::{Config, FOLDER_KEYS};
+use crate::engine::undo::{self, OpType};
+use anyhow::{Context, Result};
 use fs_err as fs;
-use ignore::WalkBuilder;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::{DirEntry, WalkBuilder};
 use infer;
 use rayon::prelude::*;
-use std::path::PathBuf;
-use std::sync::Mutex;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
-/// Information about a suspicious file extension
+/// How serious a finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A workspace health check. Each check looks at one walked entry at a time;
+/// register new ones in `all_checks`.
+pub trait AuditCheck: Send + Sync {
+    /// Stable kebab-case id used by `--check` and `audit.enabled`
+    fn id(&self) -> &'static str;
+    fn severity(&self) -> Severity;
+    /// One line describing what the check finds
+    fn description(&self) -> &'static str;
+    /// Describe the problem with `entry`, if it has one
+    fn check(&self, entry: &DirEntry) -> Option<String>;
+    /// The change that resolves `finding`, for checks that can fix it
+    fn fix(&self, _finding: &Finding) -> Option<Fix> {
+        None
+    }
+}
+
+/// A change `audit --fix` makes to resolve a finding
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Fix {
+    RemoveDir { path: PathBuf },
+    Rename { from: PathBuf, to: PathBuf },
+}
+
+impl std::fmt::Display for Fix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Fix::RemoveDir { path } => write!(f, "remove empty folder {}", path.display()),
+            Fix::Rename { from, to } => write!(
+                f,
+                "rename {} -> {}",
+                from.display(),
+                to.file_name().unwrap_or_default().to_string_lossy()
+            ),
+        }
+    }
+}
+
+/// Result of applying one fix
 #[derive(Debug, Clone)]
-pub struct SuspiciousExtension {
+pub struct FixOutcome {
+    pub fix: Fix,
+    pub error: Option<String>,
+}
+
+/// Every built-in check, in report order
+pub fn all_checks() -> Vec<Box<dyn AuditCheck>> {
+    vec![Box::new(EmptyDirs), Box::new(ExtMismatch)]
+}
+
+/// A problem found by one check
+#[derive(Debug, Clone, Serialize)]
+pub struct Finding {
+    pub check: &'static str,
+    pub severity: Severity,
     pub path: PathBuf,
-    pub declared_ext: String,
-    pub actual_ext: String,
+    pub message: String,
 }
 
 /// Report from auditing the workspace
 #[derive(Debug, Default)]
 pub struct AuditReport {
-    pub empty_folders: Vec<PathBuf>,
-    pub suspicious_extensions: Vec<SuspiciousExtension>,
+    /// Sorted by check order, then path
+    pub findings: Vec<Finding>,
+    /// Ids of the checks that ran
+    pub checks: Vec<&'static str>,
     pub items_scanned: usize,
     pub workspace_not_found: bool,
 }
@@ -30,8 +105,10 @@ impl AuditReport {
     }
 }
 
-/// Audit the workspace for issues like empty folders and mismatched file extensions
-pub fn audit_workspace(config: &Config) -> Result<AuditReport> {
+/// Audit the workspace with the checks named in `only`, or else those in
+/// `audit.enabled` (all of them when that is empty). Paths matching an
+/// `audit.ignore` glob are skipped, directories including their contents.
+pub fn audit_workspace(config: &Config, only: &[String]) -> Result<AuditReport> {
     let workspace_root = config.resolve_path("workspace");
 
     if !workspace_root.exists() {
@@ -41,51 +118,287 @@ pub fn audit_workspace(config: &Config) -> Result<AuditReport> {
         });
     }
 
+    let checks = select_checks(if only.is_empty() {
+        &config.audit.enabled
+    } else {
+        only
+    })?;
+    let ignored = ignore_globs(&config.audit.ignore)?;
+
     // Collect all entries
+    let root = workspace_root.clone();
     let entries: Vec<_> = WalkBuilder::new(&workspace_root)
+        .filter_entry(move |e| {
+            let rel = e.path().strip_prefix(&root).unwrap_or(e.path());
+            !ignored.is_match(rel)
+        })
         .build()
         .filter_map(|e| e.ok())
         .filter(|e| e.depth() >= 1)
         .collect();
 
-    let items_scanned = entries.len();
-    let report = Mutex::new(AuditReport::new());
+    let mut findings: Vec<(usize, Finding)> = entries
+        .par_iter()
+        .flat_map_iter(|entry| {
+            checks.iter().enumerate().filter_map(|(order, check)| {
+                let message = check.check(entry)?;
+                Some((
+                    order,
+                    Finding {
+                        check: check.id(),
+                        severity: check.severity(),
+                        path: entry.path().to_path_buf(),
+                        message,
+                    },
+                ))
+            })
+        })
+        .collect();
+    findings.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.path.cmp(&b.1.path)));
 
-    entries.par_iter().for_each(|entry| {
-        let path = entry.path();
+    Ok(AuditReport {
+        findings: findings.into_iter().map(|(_, f)| f).collect(),
+        checks: checks.iter().map(|c| c.id()).collect(),
+        items_scanned: entries.len(),
+        workspace_not_found: false,
+    })
+}
 
-        // Check for empty directories
-        if path.is_dir() {
-            if let Ok(mut read_dir) = fs::read_dir(path) {
-                if read_dir.next().is_none() {
-                    let mut r = report.lock().unwrap();
-                    r.empty_folders.push(path.to_path_buf());
-                }
-            }
+/// Fixes for every fixable finding in `report`. Empty folders come deepest
+/// first, and the workspace and its configured folders are never removed.
+pub fn plan_fixes(config: &Config, report: &AuditReport) -> Vec<Fix> {
+    let checks = all_checks();
+    let protected = protected_dirs(config);
+    let mut fixes: Vec<Fix> = report
+        .findings
+        .iter()
+        .filter_map(|finding| {
+            checks
+                .iter()
+                .find(|c| c.id() == finding.check)?
+                .fix(finding)
+        })
+        .filter(|fix| !matches!(fix, Fix::RemoveDir { path } if protected.contains(path)))
+        .collect();
+
+    let depth = |fix: &Fix| match fix {
+        Fix::RemoveDir { path } => path.components().count(),
+        _ => 0,
+    };
+    fixes.sort_by_key(|fix| std::cmp::Reverse(depth(fix)));
+    fixes
+}
+
+/// Apply `fixes`, journaling each change so `wardex undo` reverts it.
+/// Removing an empty folder also removes parents that are left empty, up to
+/// the configured folders. With `dry_run` nothing is touched, but the
+/// outcomes still list what would happen.
+pub fn apply_fixes(config: &Config, fixes: &[Fix], dry_run: bool) -> Vec<FixOutcome> {
+    let protected = protected_dirs(config);
+    let workspace = config.resolve_path("workspace");
+    let mut removed: HashSet<PathBuf> = HashSet::new();
+    let mut outcomes = Vec::new();
+
+    for fix in fixes {
+        let result = apply_fix(config, fix, dry_run);
+        let succeeded = result.is_ok();
+        outcomes.push(FixOutcome {
+            fix: fix.clone(),
+            error: result.err().map(|e| e.to_string()),
+        });
+
+        let Fix::RemoveDir { path } = fix else {
+            continue;
+        };
+        if !succeeded {
+            continue;
         }
+        removed.insert(path.clone());
 
-        // Check for files with mismatched extensions
-        if path.is_file() {
-            if let Ok(Some(kind)) = infer::get_from_path(path) {
-                let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-                let magic_ext = kind.extension();
-
-                if !ext.is_empty() && ext != magic_ext && !is_compatible(ext, magic_ext) {
-                    let mut r = report.lock().unwrap();
-                    r.suspicious_extensions.push(SuspiciousExtension {
-                        path: path.to_path_buf(),
-                        declared_ext: ext.to_string(),
-                        actual_ext: magic_ext.to_string(),
-                    });
-                }
+        // Bottom-up: parents emptied by this removal go too
+        let mut parent = path.parent();
+        while let Some(dir) = parent {
+            if protected.contains(dir)
+                || !dir.starts_with(&workspace)
+                || removed.contains(dir)
+                || !is_empty_without(dir, &removed)
+            {
+                break;
             }
+            let fix = Fix::RemoveDir {
+                path: dir.to_path_buf(),
+            };
+            let result = apply_fix(config, &fix, dry_run);
+            let succeeded = result.is_ok();
+            outcomes.push(FixOutcome {
+                fix,
+                error: result.err().map(|e| e.to_string()),
+            });
+            if !succeeded {
+                break;
+            }
+            removed.insert(dir.to_path_buf());
+            parent = dir.parent();
+        }
+    }
+    outcomes
+}
+
+fn apply_fix(config: &Config, fix: &Fix, dry_run: bool) -> Result<()> {
+    if let Fix::Rename { to, .. } = fix {
+        if to.exists() {
+            anyhow::bail!("{} already exists", to.display());
+        }
+    }
+    if dry_run {
+        return Ok(());
+    }
+
+    let logged = match fix {
+        Fix::RemoveDir { path } => {
+            fs::remove_dir(path)?;
+            undo::log_op(config, OpType::RemoveDir, path, path)
+        }
+        Fix::Rename { from, to } => {
+            fs::rename(from, to)?;
+            undo::log_move(config, from, to)
+        }
+    };
+    if let Err(e) = logged {
+        log::warn!("Failed to log undo operation: {}", e);
+    }
+    Ok(())
+}
+
+/// The workspace, every configured folder (inbox, projects, ...) and the CTF root
+fn protected_dirs(config: &Config) -> HashSet<PathBuf> {
+    FOLDER_KEYS
+        .iter()
+        .map(|key| config.resolve_path(key))
+        .chain([config.ctf_root()])
+        .collect()
+}
+
+/// Whether `dir` has no entries besides those in `removed`
+fn is_empty_without(dir: &Path, removed: &HashSet<PathBuf>) -> bool {
+    fs::read_dir(dir).is_ok_and(|entries| {
+        entries
+            .flatten()
+            .all(|entry| removed.contains(&entry.path()))
+    })
+}
+
+fn select_checks(ids: &[String]) -> Result<Vec<Box<dyn AuditCheck>>> {
+    let checks = all_checks();
+    if ids.is_empty() {
+        return Ok(checks);
+    }
+    if let Some(unknown) = ids.iter().find(|id| !checks.iter().any(|c| c.id() == *id)) {
+        let known: Vec<&str> = checks.iter().map(|c| c.id()).collect();
+        anyhow::bail!(
+            "Unknown audit check '{}' (available: {})",
+            unknown,
+            known.join(", ")
+        );
+    }
+    Ok(checks
+        .into_iter()
+        .filter(|c| ids.iter().any(|id| id == c.id()))
+        .collect())
+}
+
+fn ignore_globs(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(
+            Glob::new(pattern)
+                .with_context(|| format!("Invalid audit.ignore glob '{}'", pattern))?,
+        );
+    }
+    Ok(builder.build()?)
+}
+
+/// Directories with nothing in them
+struct EmptyDirs;
+
+impl AuditCheck for EmptyDirs {
+    fn id(&self) -> &'static str {
+        "empty-dirs"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn description(&self) -> &'static str {
+        "Empty folders"
+    }
+
+    fn check(&self, entry: &DirEntry) -> Option<String> {
+        if !entry.file_type()?.is_dir() {
+            return None;
+        }
+        let mut read_dir = fs::read_dir(entry.path()).ok()?;
+        read_dir
+            .next()
+            .is_none()
+            .then(|| "empty folder".to_string())
+    }
+
+    fn fix(&self, finding: &Finding) -> Option<Fix> {
+        Some(Fix::RemoveDir {
+            path: finding.path.clone(),
+        })
+    }
+}
+
+/// Files whose magic bytes say they are something else
+struct ExtMismatch;
+
+impl AuditCheck for ExtMismatch {
+    fn id(&self) -> &'static str {
+        "ext-mismatch"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn description(&self) -> &'static str {
+        "File extension doesn't match the content (magic bytes)"
+    }
+
+    fn check(&self, entry: &DirEntry) -> Option<String> {
+        if !entry.file_type()?.is_file() {
+            return None;
         }
-    });
+        let path = entry.path();
+        let kind = infer::get_from_path(path).ok()??;
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        let magic_ext = kind.extension();
 
-    let mut report = report.into_inner().unwrap();
-    report.items_scanned = items_scanned;
+        (!ext.is_empty() && ext != magic_ext && !is_compatible(&ext, magic_ext))
+            .then(|| format!("named .{}, content is .{}", ext, magic_ext))
+    }
 
-    Ok(report)
+    /// Only renames when the name says nothing about the format (e.g. a PNG
+    /// saved as .txt). A different known format may be deliberate, such as a
+    /// gzipped .tar, so that stays report-only.
+    fn fix(&self, finding: &Finding) -> Option<Fix> {
+        let ext = finding.path.extension()?.to_str()?.to_ascii_lowercase();
+        if infer::is_supported(&ext) {
+            return None;
+        }
+        let kind = infer::get_from_path(&finding.path).ok()??;
+        Some(Fix::Rename {
+            from: finding.path.clone(),
+            to: finding.path.with_extension(kind.extension()),
+        })
+    }
 }
 
 fn is_compatible(ext1: &str, ext2: &str) -> bool {
@@ -98,6 +411,53 @@ fn is_compatible(ext1: &str, ext2: &str) -> bool {
         ("html", "htm"),
         ("cc", "cpp"),
         ("cpp", "cc"),
+        ("tif", "tiff"),
+        ("tiff", "tif"),
+    ];
+    // Formats built on a generic container, keyed by the detected container
+    let containers: [(&str, &[&str]); 4] = [
+        ("gz", &["tgz", "svgz"]),
+        ("bz2", &["tbz", "tbz2"]),
+        ("xz", &["txz"]),
+        (
+            "zip",
+            &[
+                "jar", "war", "ear", "apk", "aab", "ipa", "whl", "egg", "nupkg", "vsix", "xpi",
+                "docx", "xlsx", "pptx", "odt", "ods", "odp", "epub", "kmz",
+            ],
+        ),
     ];
     pairs.contains(&(ext1, ext2))
+        || containers
+            .iter()
+            .any(|(container, names)| *container == ext2 && names.contains(&ext1))
+}
+
+/// Print findings grouped by check, at most 10 per check
+pub fn print_findings(report: &AuditReport) {
+    for check in all_checks() {
+        let findings: Vec<_> = report
+            .findings
+            .iter()
+            .filter(|f| f.check == check.id())
+            .collect();
+        if findings.is_empty() {
+            continue;
+        }
+        log::warn!(
+            "{} [{}, {}]: {}",
+            check.description(),
+            check.id(),
+            check.severity(),
+            findings.len()
+        );
+        for f in findings.iter().take(10) {
+            println!(" - {:?} ({})", f.path, f.message);
+        }
+        if findings.len() > 10 {
+            println!("... and {} more", findings.len() - 10);
+        }
+    }
+
+    log::info!("✓ Audit Complete ({}).", report.checks.join(", "));
 }
@@ -2,6 +2,7 @@ use crate::config::Config;
 use anyhow::{Context, Result};
 use chrono::prelude::*;
 use fs_err as fs;
+use log::warn;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
@@ -14,6 +15,9 @@ pub struct CtfMeta {
     pub created_at: i64,
     #[serde(default)]
     pub categories: Vec<String>,
+    /// Flag prefixes used by this event (e.g. `HTB`, `picoCTF`)
+    #[serde(default)]
+    pub flag_formats: Vec<String>,
 }
 
 impl CtfMeta {
@@ -36,6 +40,27 @@ impl CtfMeta {
             year,
             created_at: now.timestamp(),
             categories: Vec::new(),
+            flag_formats: Vec::new(),
+        }
+    }
+
+    /// Regex matching any of this event's flag formats, or `None` if none are set
+    pub fn flag_regex(&self) -> Option<String> {
+        if self.flag_formats.is_empty() {
+            return None;
+        }
+        let prefixes: Vec<String> = self.flag_formats.iter().map(|f| regex::escape(f)).collect();
+        Some(format!(r"(?:{})\{{.*?\}}", prefixes.join("|")))
+    }
+
+    /// Check a submitted flag against the event's formats.
+    /// Always true when the event has no formats configured.
+    pub fn matches_flag_format(&self, flag: &str) -> bool {
+        match self.flag_regex() {
+            Some(pattern) => regex::Regex::new(&format!("^{}$", pattern))
+                .map(|re| re.is_match(flag.trim()))
+                .unwrap_or(false),
+            None => true,
         }
     }
 
@@ -59,6 +84,18 @@ impl CtfMeta {
     }
 }
 
+/// Normalize a user-supplied flag format to its bare prefix.
+///
+/// Accepts `HTB`, `HTB{`, `HTB{}` or `HTB{...}` and returns `HTB`.
+pub fn normalize_flag_format(format: &str) -> String {
+    let format = format.trim();
+    let prefix = match format.find('{') {
+        Some(idx) => &format[..idx],
+        None => format,
+    };
+    prefix.to_string()
+}
+
 /// Result of creating a CTF event
 #[derive(Debug)]
 pub struct CreateEventResult {
@@ -73,7 +110,10 @@ pub struct CtfEventInfo {
     pub name: String,
     pub year: i32,
     pub date: Option<String>,
+    /// Live challenges plus those `ctf solve` moved to the archives
     pub challenge_count: usize,
+    /// Live challenges with a `flag.txt`, plus archived ones
+    pub solved_count: usize,
     pub path: PathBuf,
     pub has_metadata: bool,
 }
@@ -88,6 +128,7 @@ pub fn create_event(
     config: &Config,
     name: &str,
     date: Option<String>,
+    flag_formats: &[String],
 ) -> Result<CreateEventResult> {
     let ctf_root = config.ctf_root();
 
@@ -122,6 +163,11 @@ pub fn create_event(
     // Save metadata
     let mut meta = meta;
     meta.categories = categories_created.clone();
+    meta.flag_formats = flag_formats
+        .iter()
+        .map(|f| normalize_flag_format(f))
+        .filter(|f| !f.is_empty())
+        .collect();
     meta.save(&event_dir)?;
 
     // Auto-set active event
@@ -159,12 +205,13 @@ pub fn list_events(config: &Config) -> Result<ListEventsResult> {
 
         // Try to load metadata first
         if let Some(meta) = CtfMeta::load(&path) {
-            let challenge_count = count_challenges(&path);
+            let (challenge_count, solved_count) = count_challenges(&path);
             events.push(CtfEventInfo {
                 name: meta.name,
                 year: meta.year,
                 date: Some(meta.date),
                 challenge_count,
+                solved_count,
                 path,
                 has_metadata: true,
             });
@@ -185,7 +232,7 @@ pub fn list_events(config: &Config) -> Result<ListEventsResult> {
                         }
                         let sub_path = sub.path();
                         let sub_name = sub.file_name().to_string_lossy().to_string();
-                        let challenge_count = count_challenges(&sub_path);
+                        let (challenge_count, solved_count) = count_challenges(&sub_path);
 
                         // Check for metadata in subdirectory
                         let (name, date, has_meta) = if let Some(meta) = CtfMeta::load(&sub_path) {
@@ -199,18 +246,20 @@ pub fn list_events(config: &Config) -> Result<ListEventsResult> {
                             year,
                             date,
                             challenge_count,
+                            solved_count,
                             path: sub_path,
                             has_metadata: has_meta,
                         });
                     }
                 }
             } else {
-                let challenge_count = count_challenges(&path);
+                let (challenge_count, solved_count) = count_challenges(&path);
                 events.push(CtfEventInfo {
                     name: dir_name,
                     year,
                     date: None,
                     challenge_count,
+                    solved_count,
                     path,
                     has_metadata: false,
                 });
@@ -218,6 +267,16 @@ pub fn list_events(config: &Config) -> Result<ListEventsResult> {
         }
     }
 
+    // `ctf solve` moves solved challenges to archives/CTFs/<event dir>/<category>
+    let archived_root = config.resolve_path("archives").join("CTFs");
+    for event in &mut events {
+        if let Some(dir_name) = event.path.file_name() {
+            let (archived, _) = count_challenges(&archived_root.join(dir_name));
+            event.challenge_count += archived;
+            event.solved_count += archived;
+        }
+    }
+
     events.sort_by(|a, b| b.year.cmp(&a.year).then_with(|| a.name.cmp(&b.name)));
 
     Ok(ListEventsResult {
@@ -226,18 +285,25 @@ pub fn list_events(config: &Config) -> Result<ListEventsResult> {
     })
 }
 
-fn count_challenges(event_dir: &Path) -> usize {
+/// Challenges (event/<category>/<challenge>) and how many of them are solved
+fn count_challenges(event_dir: &Path) -> (usize, usize) {
     let mut count = 0;
+    let mut solved = 0;
     if let Ok(cats) = fs::read_dir(event_dir) {
         for cat in cats.flatten() {
             if cat.path().is_dir() && cat.file_name() != ".git" {
                 if let Ok(chals) = fs::read_dir(cat.path()) {
-                    count += chals.flatten().filter(|c| c.path().is_dir()).count();
+                    for chal in chals.flatten().filter(|c| c.path().is_dir()) {
+                        count += 1;
+                        if chal.path().join("flag.txt").is_file() {
+                            solved += 1;
+                        }
+                    }
                 }
             }
         }
     }
-    count
+    (count, solved)
 }
 
 pub fn import_challenge(
@@ -744,13 +810,39 @@ pub fn get_event_path(
     Ok(event_path)
 }
 
-pub fn solve_challenge(config: &Config, flag: &str) -> Result<()> {
+pub fn solve_challenge(config: &Config, flag: &str, force: bool) -> Result<()> {
     let current_dir = std::env::current_dir()?;
     let dir_name = current_dir
         .file_name()
         .and_then(|n| n.to_str())
         .ok_or_else(|| anyhow::anyhow!("Invalid directory name"))?;
 
+    // 0. Validate flag against the event's format
+    if let Some(meta) = find_event_root().and_then(|root| CtfMeta::load(&root)) {
+        if !meta.matches_flag_format(flag) {
+            let expected: Vec<String> = meta
+                .flag_formats
+                .iter()
+                .map(|f| format!("{}{{...}}", f))
+                .collect();
+            if force {
+                warn!(
+                    "Flag does not match event format ({}), saving anyway.",
+                    expected.join(", ")
+                );
+            } else {
+                anyhow::bail!(
+                    "Flag {:?} does not match the format for {}.\n\n\
+                    Expected: {}\n\
+                    Tip: Use --force to save it anyway.",
+                    flag,
+                    meta.name,
+                    expected.join(", ")
+                );
+            }
+        }
+    }
+
     // 1. Save flag
     let flag_path = current_dir.join("flag.txt");
     fs::write(&flag_path, flag)?;
@@ -786,9 +878,9 @@ pub fn solve_challenge(config: &Config, flag: &str) -> Result<()> {
         let script_path = current_dir.join(script_name);
         if let Ok(content) = fs::read_to_string(&script_path) {
             let notes_path = current_dir.join("notes.md");
-            let mut notes_content = fs::read_to_string(&notes_path).unwrap_or_default();
+            let notes_content = fs::read_to_string(&notes_path).unwrap_or_default();
 
-            let ext = script_name.split('.').last().unwrap_or("");
+            let ext = script_name.split('.').next_back().unwrap_or("");
             let header = format!("\n\n## Solution Code ({})\n\n```{}\n", script_name, ext);
             let footer = "\n```\n";
 
@@ -905,7 +997,12 @@ fn create_zip(src_dir: &Path, dest_file: &Path) -> Result<()> {
 
 /// Walk up directory tree to find CTF event root (containing .ctf_meta.json)
 pub fn find_event_root() -> Option<PathBuf> {
-    let mut current = std::env::current_dir().ok()?;
+    find_event_root_from(&std::env::current_dir().ok()?)
+}
+
+/// Walk up from `start` to find the CTF event root containing it
+pub fn find_event_root_from(start: &Path) -> Option<PathBuf> {
+    let mut current = fs::canonicalize(start).ok()?;
     loop {
         if current.join(".ctf_meta.json").exists() {
             return Some(current);
@@ -917,6 +1014,16 @@ pub fn find_event_root() -> Option<PathBuf> {
     None
 }
 
+/// Flag regex of the event containing `path`, falling back to the event
+/// containing the current directory. `None` if neither is inside an event
+/// or the event has no flag formats.
+pub fn event_flag_pattern(path: &Path) -> Option<(String, String)> {
+    let root = find_event_root_from(path).or_else(find_event_root)?;
+    let meta = CtfMeta::load(&root)?;
+    let pattern = meta.flag_regex()?;
+    Some((meta.name, pattern))
+}
+
 /// Get the active event root from local context or global state
 pub fn get_active_event_root() -> Result<PathBuf> {
     // 1. Try local context (walking up)
@@ -965,6 +1072,14 @@ pub fn get_context_info(_config: &Config) -> Result<()> {
 
     println!("Current Event: {} ({})", meta.name, meta.year);
     println!("Root: {:?}", root);
+    if !meta.flag_formats.is_empty() {
+        let formats: Vec<String> = meta
+            .flag_formats
+            .iter()
+            .map(|f| format!("{}{{...}}", f))
+            .collect();
+        println!("Flag Format: {}", formats.join(", "));
+    }
     println!(
         "Source: {}",
         if is_local {
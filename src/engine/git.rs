@@ -0,0 +1,162 @@
+use crate::config::Config;
+use crate::engine::status::{self, RepoKind, RepoStatus, SyncStatus};
+use anyhow::{Context, Result};
+use globset::{Glob, GlobMatcher};
+use rayon::prelude::*;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// A git command run in every selected repository
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitOp {
+    Fetch,
+    Pull { ff_only: bool },
+    Push,
+}
+
+impl GitOp {
+    fn args(&self) -> Vec<&'static str> {
+        match self {
+            GitOp::Fetch => vec!["fetch", "--all", "--prune"],
+            GitOp::Pull { ff_only: true } => vec!["pull", "--ff-only"],
+            GitOp::Pull { ff_only: false } => vec!["pull"],
+            GitOp::Push => vec!["push"],
+        }
+    }
+}
+
+/// Which repositories a bulk operation applies to
+#[derive(Debug, Clone, Default)]
+pub struct RepoFilter {
+    pub dirty: bool,
+    /// Behind upstream as of the last fetch
+    pub behind: bool,
+    /// Glob matched against the repo path relative to the workspace, or its name
+    pub path: Option<String>,
+}
+
+impl RepoFilter {
+    fn matcher(&self) -> Result<Option<GlobMatcher>> {
+        self.path
+            .as_deref()
+            .map(|p| {
+                Glob::new(p)
+                    .map(|g| g.compile_matcher())
+                    .with_context(|| format!("Invalid --path glob '{}'", p))
+            })
+            .transpose()
+    }
+
+    fn matches(&self, repo: &RepoStatus, workspace: &Path, glob: Option<&GlobMatcher>) -> bool {
+        let behind = matches!(
+            repo.sync_status,
+            SyncStatus::Behind(_) | SyncStatus::Diverged { .. }
+        );
+        let rel = repo.path.strip_prefix(workspace).unwrap_or(&repo.path);
+        (!self.dirty || repo.is_dirty)
+            && (!self.behind || behind)
+            && glob.is_none_or(|g| g.is_match(rel) || g.is_match(&repo.name))
+    }
+}
+
+/// Outcome of the operation in one repository
+#[derive(Debug, Clone)]
+pub struct GitOpResult {
+    pub name: String,
+    pub path: PathBuf,
+    pub success: bool,
+    /// Last line git printed, or a short summary
+    pub detail: String,
+    /// Sync state after the operation
+    pub sync: String,
+}
+
+#[derive(Debug, Default)]
+pub struct GitOpReport {
+    pub results: Vec<GitOpResult>,
+    pub workspace_not_found: bool,
+}
+
+impl GitOpReport {
+    pub fn failed(&self) -> usize {
+        self.results.iter().filter(|r| !r.success).count()
+    }
+}
+
+/// Run `op` in every repository found by `status::show_status` that passes
+/// `filter`, at most `jobs` at a time.
+pub fn run_bulk(
+    config: &Config,
+    op: GitOp,
+    filter: &RepoFilter,
+    jobs: usize,
+) -> Result<GitOpReport> {
+    // Filtering on dirty needs exact change counts, so skip the status cache
+    let status_report = status::show_status(config, filter.dirty)?;
+    if status_report.workspace_not_found {
+        return Ok(GitOpReport {
+            workspace_not_found: true,
+            ..Default::default()
+        });
+    }
+
+    let workspace = config.resolve_path("workspace");
+    let glob = filter.matcher()?;
+    let repos: Vec<RepoStatus> = status_report
+        .repos
+        .into_iter()
+        .filter(|r| filter.matches(r, &workspace, glob.as_ref()))
+        // Only fetch makes sense without a work tree
+        .filter(|r| r.kind != RepoKind::Bare || op == GitOp::Fetch)
+        .collect();
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs.max(1))
+        .build()?;
+    let results = pool.install(|| repos.par_iter().map(|r| run_in_repo(op, r)).collect());
+
+    Ok(GitOpReport {
+        results,
+        workspace_not_found: false,
+    })
+}
+
+fn run_in_repo(op: GitOp, repo: &RepoStatus) -> GitOpResult {
+    let output = Command::new("git")
+        .args(op.args())
+        .current_dir(&repo.path)
+        // Never block a parallel run on a credential prompt
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(std::process::Stdio::null())
+        .output();
+
+    let (success, detail) = match output {
+        Ok(out) => {
+            let text = format!(
+                "{}\n{}",
+                String::from_utf8_lossy(&out.stdout),
+                String::from_utf8_lossy(&out.stderr)
+            );
+            let last_line = text
+                .lines()
+                .map(str::trim)
+                .rfind(|l| !l.is_empty() && !l.starts_with("hint:"))
+                .unwrap_or("Up to date")
+                .to_string();
+            (out.status.success(), last_line)
+        }
+        Err(e) => (false, format!("Failed to run git: {}", e)),
+    };
+
+    let sync = status::analyze_repo(&repo.path)
+        .map(|r| r.sync_status.display())
+        .unwrap_or_else(|_| repo.sync_status.display());
+
+    GitOpResult {
+        name: repo.name.clone(),
+        path: repo.path.clone(),
+        success,
+        detail,
+        sync,
+    }
+}
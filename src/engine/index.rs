@@ -0,0 +1,651 @@
+//! Persistent trigram index backing `wardex grep`.
+//!
+//! One index file per workspace lives in the user cache dir. It records every
+//! file under the grep roots (path, mtime, size) plus, for each trigram, the
+//! sorted list of files containing it. A query extracts the literals every
+//! match must start (or end) with, intersects their posting lists and only
+//! greps the surviving candidates. Files that changed since indexing, and new
+//! files in directories that changed, are always scanned live so results are
+//! never stale — the index only ever narrows the set of files to read.
+//!
+//! On-disk layout (little endian):
+//!
+//! ```text
+//! magic | roots | dirs (path, mtime) | files (path, mtime, size, indexed)
+//!       | trigram count | [trigram u32, offset u64, len u32]*  (sorted)
+//!       | postings (varint-encoded file id deltas)
+//! ```
+
+use crate::config::Config;
+use crate::engine::search;
+use anyhow::{bail, Context, Result};
+use ignore::WalkBuilder;
+use rayon::prelude::*;
+use regex_syntax::hir::literal::{ExtractKind, Extractor};
+use serde::Serialize;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File, Metadata};
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+const MAGIC: &[u8; 8] = b"WXIDX01\n";
+/// Size of one entry in the trigram table
+const RECORD_LEN: u64 = 16;
+
+#[derive(Debug, Clone)]
+struct FileEntry {
+    path: PathBuf,
+    mtime: i64,
+    size: u64,
+    /// False for files over the size limit; those are always grepped live
+    indexed: bool,
+}
+
+#[derive(Debug, Clone)]
+struct DirEntry {
+    path: PathBuf,
+    mtime: i64,
+}
+
+/// Summary of a `wardex index` run
+#[derive(Debug, Default, Serialize)]
+pub struct IndexReport {
+    pub index_path: PathBuf,
+    pub files: usize,
+    /// Files whose content was (re)read this run
+    pub files_read: usize,
+    /// Unchanged files carried over from the previous index
+    pub files_reused: usize,
+    /// Files over `index.max_file_size_mb`, always grepped live
+    pub files_unindexed: usize,
+    pub trigrams: usize,
+    pub size_bytes: u64,
+}
+
+/// Files to grep for one query, as narrowed down by the index
+#[derive(Debug, Default)]
+pub struct GrepPlan {
+    pub files: Vec<PathBuf>,
+    /// Number of files known to the index
+    pub indexed_files: usize,
+    /// Files changed or created since the index was built
+    pub stale: usize,
+    /// Whether the pattern yielded literals the trigram filter could use
+    pub filtered: bool,
+}
+
+/// An opened index file. Posting lists are read on demand.
+pub struct ContentIndex {
+    file: File,
+    roots: Vec<PathBuf>,
+    dirs: Vec<DirEntry>,
+    files: Vec<FileEntry>,
+    table_start: u64,
+    trigram_count: u64,
+    postings_start: u64,
+}
+
+/// Location of the index file for the configured workspace
+pub fn index_path(config: &Config) -> Result<PathBuf> {
+    workspace_cache_file(config, "content", "idx")
+}
+
+/// `<cache dir>/wardex/<prefix>-<workspace hash>.<ext>`, so several
+/// workspaces can share one cache directory
+pub(crate) fn workspace_cache_file(config: &Config, prefix: &str, ext: &str) -> Result<PathBuf> {
+    let cache = dirs::cache_dir().context("Could not determine cache directory")?;
+    let workspace = config.resolve_path("workspace");
+    let key = fnv1a(workspace.to_string_lossy().as_bytes());
+    Ok(cache
+        .join("wardex")
+        .join(format!("{}-{:016x}.{}", prefix, key, ext)))
+}
+
+/// Build or incrementally refresh the index. Unchanged files (same mtime and
+/// size) keep their trigrams from the previous index; only new or modified
+/// files are read. `rebuild` discards the previous index entirely.
+pub fn build_index(config: &Config, rebuild: bool) -> Result<IndexReport> {
+    let roots: Vec<PathBuf> = search::content_roots(config)
+        .into_iter()
+        .filter(|r| r.exists())
+        .collect();
+    let max_size = config.index.max_file_size();
+
+    let previous = if rebuild {
+        None
+    } else {
+        ContentIndex::open(config).ok().flatten()
+    };
+
+    let (dirs, files) = walk_roots(&roots, max_size);
+
+    let old_ids: HashMap<&Path, usize> = previous
+        .as_ref()
+        .map(|p| {
+            p.files
+                .iter()
+                .enumerate()
+                .map(|(id, f)| (f.path.as_path(), id))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // old id -> new id for every file that can be carried over
+    let mut remap: Vec<Option<u32>> = vec![None; old_ids.len()];
+    let mut to_read = Vec::new();
+    let mut report = IndexReport {
+        files: files.len(),
+        ..Default::default()
+    };
+
+    for (new_id, file) in files.iter().enumerate() {
+        if !file.indexed {
+            report.files_unindexed += 1;
+            continue;
+        }
+        let reusable = old_ids.get(file.path.as_path()).and_then(|&old_id| {
+            let old = &previous.as_ref()?.files[old_id];
+            (old.indexed && old.mtime == file.mtime && old.size == file.size).then_some(old_id)
+        });
+        match reusable {
+            Some(old_id) => {
+                remap[old_id] = Some(new_id as u32);
+                report.files_reused += 1;
+            }
+            None => to_read.push(new_id),
+        }
+    }
+
+    let mut postings: HashMap<u32, Vec<u32>> = HashMap::new();
+    if let Some(prev) = &previous {
+        if report.files_reused > 0 {
+            prev.carry_over(&remap, &mut postings)?;
+        }
+    }
+
+    let read: Vec<(u32, Vec<u32>)> = to_read
+        .par_iter()
+        .filter_map(|&id| {
+            let data = fs::read(&files[id].path).ok()?;
+            Some((id as u32, trigrams_of(&data)))
+        })
+        .collect();
+    report.files_read = read.len();
+    for (id, trigrams) in read {
+        for t in trigrams {
+            postings.entry(t).or_default().push(id);
+        }
+    }
+
+    let path = index_path(config)?;
+    report.trigrams = postings.len();
+    report.size_bytes = write_index(&path, &roots, &dirs, &files, postings)?;
+    report.index_path = path;
+    Ok(report)
+}
+
+impl ContentIndex {
+    /// Open the workspace index. Returns `None` when no index exists or it
+    /// was built for a different set of roots.
+    pub fn open(config: &Config) -> Result<Option<Self>> {
+        let path = index_path(config)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        let index = Self::open_path(&path)
+            .with_context(|| format!("Failed to read index {}", path.display()))?;
+
+        let roots: Vec<PathBuf> = search::content_roots(config)
+            .into_iter()
+            .filter(|r| r.exists())
+            .collect();
+        if index.roots != roots {
+            return Ok(None);
+        }
+        Ok(Some(index))
+    }
+
+    fn open_path(path: &Path) -> Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            bail!("not a wardex index (or an incompatible version)");
+        }
+
+        let roots = (0..read_u32(&mut reader)?)
+            .map(|_| read_path(&mut reader))
+            .collect::<Result<_>>()?;
+        let dirs = (0..read_u32(&mut reader)?)
+            .map(|_| {
+                Ok(DirEntry {
+                    path: read_path(&mut reader)?,
+                    mtime: read_i64(&mut reader)?,
+                })
+            })
+            .collect::<Result<_>>()?;
+        let files = (0..read_u32(&mut reader)?)
+            .map(|_| {
+                Ok(FileEntry {
+                    path: read_path(&mut reader)?,
+                    mtime: read_i64(&mut reader)?,
+                    size: read_u64(&mut reader)?,
+                    indexed: read_u8(&mut reader)? != 0,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        let trigram_count = read_u32(&mut reader)? as u64;
+        let table_start = reader.stream_position()?;
+        let postings_start = table_start + trigram_count * RECORD_LEN;
+
+        Ok(Self {
+            file: reader.into_inner(),
+            roots,
+            dirs,
+            files,
+            table_start,
+            trigram_count,
+            postings_start,
+        })
+    }
+
+    /// Decide which files need to be grepped for `pattern`
+    pub fn plan(&self, pattern: &str) -> Result<GrepPlan> {
+        let allowed = match required_literals(pattern) {
+            Some(literals) => Some(self.candidate_ids(&literals)?),
+            None => None,
+        };
+
+        let mut plan = GrepPlan {
+            indexed_files: self.files.len(),
+            filtered: allowed.is_some(),
+            ..Default::default()
+        };
+
+        let fresh: Vec<Option<bool>> = self
+            .files
+            .par_iter()
+            .map(|f| {
+                fs::metadata(&f.path)
+                    .ok()
+                    .map(|m| mtime_ns(&m) == f.mtime && m.len() == f.size)
+            })
+            .collect();
+
+        for (id, (file, fresh)) in self.files.iter().zip(fresh).enumerate() {
+            match fresh {
+                // Deleted since indexing
+                None => {}
+                Some(false) => {
+                    plan.stale += 1;
+                    plan.files.push(file.path.clone());
+                }
+                Some(true) => {
+                    let keep = !file.indexed
+                        || allowed
+                            .as_ref()
+                            .is_none_or(|ids| ids.contains(&(id as u32)));
+                    if keep {
+                        plan.files.push(file.path.clone());
+                    }
+                }
+            }
+        }
+
+        let new_files = self.new_files();
+        plan.stale += new_files.len();
+        plan.files.extend(new_files);
+        plan.files.sort();
+        plan.files.dedup();
+        Ok(plan)
+    }
+
+    /// Files that appeared in directories modified since indexing
+    fn new_files(&self) -> Vec<PathBuf> {
+        let known_files: HashSet<&Path> = self.files.iter().map(|f| f.path.as_path()).collect();
+        let known_dirs: HashSet<&Path> = self.dirs.iter().map(|d| d.path.as_path()).collect();
+
+        let changed: Vec<&DirEntry> = self
+            .dirs
+            .par_iter()
+            .filter(|d| {
+                fs::metadata(&d.path)
+                    .map(|m| mtime_ns(&m) != d.mtime)
+                    .unwrap_or(false)
+            })
+            .collect();
+
+        let mut found = Vec::new();
+        for dir in changed {
+            for entry in WalkBuilder::new(&dir.path)
+                .max_depth(Some(1))
+                .build()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.depth() == 1)
+            {
+                let path = entry.path();
+                let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+                if is_dir && !known_dirs.contains(path) {
+                    // A whole new subtree: everything in it is new
+                    found.extend(
+                        WalkBuilder::new(path)
+                            .build()
+                            .filter_map(|e| e.ok())
+                            .filter(|e| e.file_type().is_some_and(|t| t.is_file()))
+                            .map(|e| e.into_path()),
+                    );
+                } else if !is_dir && !known_files.contains(path) {
+                    found.push(path.to_path_buf());
+                }
+            }
+        }
+        found
+    }
+
+    /// Ids of indexed files that may contain at least one of `literals`
+    fn candidate_ids(&self, literals: &[Vec<u8>]) -> Result<HashSet<u32>> {
+        let mut cache: HashMap<u32, Vec<u32>> = HashMap::new();
+        let mut result = HashSet::new();
+
+        for literal in literals {
+            let mut ids: Option<Vec<u32>> = None;
+            for t in trigrams_of(literal) {
+                let list = match cache.entry(t) {
+                    Entry::Occupied(e) => e.into_mut(),
+                    Entry::Vacant(e) => e.insert(self.postings(t)?),
+                };
+                ids = Some(match ids {
+                    None => list.clone(),
+                    Some(acc) => intersect(&acc, list),
+                });
+                if ids.as_ref().is_some_and(|i| i.is_empty()) {
+                    break;
+                }
+            }
+            result.extend(ids.unwrap_or_default());
+        }
+        Ok(result)
+    }
+
+    /// Posting list for a trigram, found by binary search over the table
+    fn postings(&self, trigram: u32) -> Result<Vec<u32>> {
+        let mut file = &self.file;
+        let (mut lo, mut hi) = (0u64, self.trigram_count);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            file.seek(SeekFrom::Start(self.table_start + mid * RECORD_LEN))?;
+            let (t, offset, len) = read_record(&mut file)?;
+            match t.cmp(&trigram) {
+                std::cmp::Ordering::Less => lo = mid + 1,
+                std::cmp::Ordering::Greater => hi = mid,
+                std::cmp::Ordering::Equal => {
+                    file.seek(SeekFrom::Start(self.postings_start + offset))?;
+                    let mut buf = vec![0u8; len as usize];
+                    file.read_exact(&mut buf)?;
+                    return Ok(decode_postings(&buf));
+                }
+            }
+        }
+        Ok(Vec::new())
+    }
+
+    /// Copy posting entries of carried-over files into `postings`, renumbered
+    fn carry_over(
+        &self,
+        remap: &[Option<u32>],
+        postings: &mut HashMap<u32, Vec<u32>>,
+    ) -> Result<()> {
+        let mut file = &self.file;
+        file.seek(SeekFrom::Start(self.table_start))?;
+        let mut reader = BufReader::new(file);
+        let records = (0..self.trigram_count)
+            .map(|_| read_record(&mut reader))
+            .collect::<Result<Vec<_>>>()?;
+
+        // Posting lists are stored in table order, so read them sequentially
+        for (trigram, _, len) in records {
+            let mut buf = vec![0u8; len as usize];
+            reader.read_exact(&mut buf)?;
+            let ids: Vec<u32> = decode_postings(&buf)
+                .into_iter()
+                .filter_map(|old| remap.get(old as usize).copied().flatten())
+                .collect();
+            if !ids.is_empty() {
+                postings.entry(trigram).or_default().extend(ids);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Literals every match of `pattern` must start or end with, if they are all
+/// long enough to yield trigrams. `None` means the index cannot narrow the
+/// search and every file has to be grepped.
+fn required_literals(pattern: &str) -> Option<Vec<Vec<u8>>> {
+    let hir = regex_syntax::Parser::new().parse(pattern).ok()?;
+    [ExtractKind::Prefix, ExtractKind::Suffix]
+        .into_iter()
+        .find_map(|kind| {
+            let seq = Extractor::new().kind(kind).extract(&hir);
+            let literals = seq.literals()?;
+            (!literals.is_empty() && literals.iter().all(|l| l.len() >= 3))
+                .then(|| literals.iter().map(|l| l.as_bytes().to_vec()).collect())
+        })
+}
+
+fn walk_roots(roots: &[PathBuf], max_size: Option<u64>) -> (Vec<DirEntry>, Vec<FileEntry>) {
+    let mut dirs = Vec::new();
+    let mut files = Vec::new();
+
+    for root in roots {
+        for entry in WalkBuilder::new(root)
+            .sort_by_file_name(|a, b| a.cmp(b))
+            .build()
+            .filter_map(|e| e.ok())
+        {
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            if meta.is_dir() {
+                dirs.push(DirEntry {
+                    path: entry.into_path(),
+                    mtime: mtime_ns(&meta),
+                });
+            } else if meta.is_file() {
+                files.push(FileEntry {
+                    path: entry.into_path(),
+                    mtime: mtime_ns(&meta),
+                    size: meta.len(),
+                    indexed: max_size.is_none_or(|max| meta.len() <= max),
+                });
+            }
+        }
+    }
+    (dirs, files)
+}
+
+fn write_index(
+    path: &Path,
+    roots: &[PathBuf],
+    dirs: &[DirEntry],
+    files: &[FileEntry],
+    postings: HashMap<u32, Vec<u32>>,
+) -> Result<u64> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut table: Vec<(u32, Vec<u32>)> = postings.into_iter().collect();
+    table.sort_unstable_by_key(|(t, _)| *t);
+
+    let tmp = path.with_extension("idx.tmp");
+    let mut out = BufWriter::new(File::create(&tmp)?);
+    out.write_all(MAGIC)?;
+
+    out.write_all(&(roots.len() as u32).to_le_bytes())?;
+    for root in roots {
+        write_path(&mut out, root)?;
+    }
+    out.write_all(&(dirs.len() as u32).to_le_bytes())?;
+    for dir in dirs {
+        write_path(&mut out, &dir.path)?;
+        out.write_all(&dir.mtime.to_le_bytes())?;
+    }
+    out.write_all(&(files.len() as u32).to_le_bytes())?;
+    for file in files {
+        write_path(&mut out, &file.path)?;
+        out.write_all(&file.mtime.to_le_bytes())?;
+        out.write_all(&file.size.to_le_bytes())?;
+        out.write_all(&[file.indexed as u8])?;
+    }
+
+    let encoded: Vec<(u32, Vec<u8>)> = table
+        .into_par_iter()
+        .map(|(t, mut ids)| {
+            ids.sort_unstable();
+            ids.dedup();
+            (t, encode_postings(&ids))
+        })
+        .collect();
+
+    out.write_all(&(encoded.len() as u32).to_le_bytes())?;
+    let mut offset = 0u64;
+    for (t, bytes) in &encoded {
+        out.write_all(&t.to_le_bytes())?;
+        out.write_all(&offset.to_le_bytes())?;
+        out.write_all(&(bytes.len() as u32).to_le_bytes())?;
+        offset += bytes.len() as u64;
+    }
+    for (_, bytes) in &encoded {
+        out.write_all(bytes)?;
+    }
+
+    out.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+    fs::rename(&tmp, path)?;
+    Ok(fs::metadata(path)?.len())
+}
+
+/// Distinct ASCII-lowercased trigrams of `data`, sorted
+fn trigrams_of(data: &[u8]) -> Vec<u32> {
+    let mut trigrams: Vec<u32> = data
+        .windows(3)
+        .map(|w| {
+            u32::from(w[0].to_ascii_lowercase()) << 16
+                | u32::from(w[1].to_ascii_lowercase()) << 8
+                | u32::from(w[2].to_ascii_lowercase())
+        })
+        .collect();
+    trigrams.sort_unstable();
+    trigrams.dedup();
+    trigrams
+}
+
+fn intersect(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (mut i, mut j) = (0, 0);
+    let mut out = Vec::new();
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                out.push(a[i]);
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    out
+}
+
+fn encode_postings(ids: &[u32]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(ids.len());
+    let mut prev = 0u32;
+    for &id in ids {
+        let mut delta = id - prev;
+        prev = id;
+        loop {
+            let byte = (delta & 0x7f) as u8;
+            delta >>= 7;
+            if delta == 0 {
+                out.push(byte);
+                break;
+            }
+            out.push(byte | 0x80);
+        }
+    }
+    out
+}
+
+fn decode_postings(buf: &[u8]) -> Vec<u32> {
+    let mut ids = Vec::new();
+    let (mut current, mut value, mut shift) = (0u32, 0u32, 0u32);
+    for &byte in buf {
+        value |= u32::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            current += value;
+            ids.push(current);
+            value = 0;
+            shift = 0;
+        } else {
+            shift += 7;
+        }
+    }
+    ids
+}
+
+fn mtime_ns(meta: &Metadata) -> i64 {
+    meta.modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos() as i64)
+        .unwrap_or(0)
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn write_path(out: &mut impl Write, path: &Path) -> Result<()> {
+    let bytes = path.to_string_lossy();
+    out.write_all(&(bytes.len() as u32).to_le_bytes())?;
+    out.write_all(bytes.as_bytes())?;
+    Ok(())
+}
+
+fn read_path(reader: &mut impl Read) -> Result<PathBuf> {
+    let len = read_u32(reader)? as usize;
+    let mut buf = vec![0u8; len];
+    reader.read_exact(&mut buf)?;
+    Ok(PathBuf::from(String::from_utf8(buf)?))
+}
+
+fn read_record(reader: &mut impl Read) -> Result<(u32, u64, u32)> {
+    Ok((read_u32(reader)?, read_u64(reader)?, read_u32(reader)?))
+}
+
+fn read_u8(reader: &mut impl Read) -> Result<u8> {
+    let mut buf = [0u8; 1];
+    reader.read_exact(&mut buf)?;
+    Ok(buf[0])
+}
+
+fn read_u32(reader: &mut impl Read) -> Result<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64(reader: &mut impl Read) -> Result<u64> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn read_i64(reader: &mut impl Read) -> Result<i64> {
+    Ok(read_u64(reader)? as i64)
+}
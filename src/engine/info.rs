@@ -0,0 +1,362 @@
+use crate::config::Config;
+use crate::engine::{ctf, status};
+use crate::utils::output;
+use anyhow::Result;
+use fs_err as fs;
+use git2::Repository;
+use ignore::WalkBuilder;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Everything wardex knows about a file or directory
+#[derive(Debug, Default, Serialize)]
+pub struct PathInfo {
+    pub path: PathBuf,
+    /// "file", "directory" or "symlink"
+    pub kind: String,
+    /// Recursive size of non-ignored files
+    pub size_bytes: u64,
+    pub file_count: usize,
+    /// Last modification, RFC 3339 in local time
+    pub modified: Option<String>,
+    /// Languages by file count, most common first
+    pub languages: Vec<LanguageShare>,
+    /// MIME type detected from magic bytes (files only)
+    pub magic: Option<String>,
+    pub git: Option<GitInfo>,
+    pub para: Option<ParaInfo>,
+    pub ctf: Option<CtfInfo>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LanguageShare {
+    pub language: String,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// State of the repository containing the path
+#[derive(Debug, Clone, Serialize)]
+pub struct GitInfo {
+    pub root: PathBuf,
+    pub branch: Option<String>,
+    pub is_dirty: bool,
+    /// Human summary such as "Synced", "↑ 2" or "Local"
+    pub sync: String,
+    pub ahead: usize,
+    pub behind: usize,
+}
+
+/// PARA folder the path lives in
+#[derive(Debug, Clone, Serialize)]
+pub struct ParaInfo {
+    /// Folder key: inbox, projects, areas, resources or archives
+    pub area: String,
+    /// Top-level entry of that folder containing the path
+    pub item: Option<String>,
+}
+
+/// CTF event/challenge the path belongs to
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CtfInfo {
+    pub event: String,
+    pub category: Option<String>,
+    pub challenge: Option<String>,
+    /// Flag saved by `ctf solve`, when the path is inside a solved challenge
+    pub flag: Option<String>,
+    /// Challenges below the path, and how many of them have a flag
+    pub challenges: usize,
+    pub solved: usize,
+}
+
+/// Inspect `path`
+pub fn inspect(config: &Config, path: &Path) -> Result<PathInfo> {
+    let is_symlink = fs::symlink_metadata(path)?.file_type().is_symlink();
+    let path = fs::canonicalize(path)?;
+    let meta = fs::metadata(&path)?;
+
+    let mut info = PathInfo {
+        kind: if is_symlink {
+            "symlink"
+        } else if meta.is_dir() {
+            "directory"
+        } else {
+            "file"
+        }
+        .to_string(),
+        modified: meta.modified().ok().map(|t| {
+            chrono::DateTime::<chrono::Local>::from(t)
+                .to_rfc3339_opts(chrono::SecondsFormat::Secs, false)
+        }),
+        git: git_info(&path),
+        para: para_info(config, &path),
+        ctf: ctf_info(config, &path),
+        ..Default::default()
+    };
+
+    if meta.is_dir() {
+        scan_dir(&path, &mut info);
+    } else {
+        info.size_bytes = meta.len();
+        info.file_count = 1;
+        info.magic = infer::get_from_path(&path)
+            .ok()
+            .flatten()
+            .map(|kind| kind.mime_type().to_string());
+        if let Some(language) = path_language(&path) {
+            info.languages.push(LanguageShare {
+                language: language.to_string(),
+                files: 1,
+                bytes: meta.len(),
+            });
+        }
+    }
+
+    info.path = path;
+    Ok(info)
+}
+
+/// Size, file count and languages of a directory, honouring .gitignore
+fn scan_dir(path: &Path, info: &mut PathInfo) {
+    let mut languages: HashMap<&'static str, (usize, u64)> = HashMap::new();
+
+    let walker = WalkBuilder::new(path)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(|e| e.file_name() != ".git")
+        .build();
+    for entry in walker.filter_map(|e| e.ok()) {
+        if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+            continue;
+        }
+        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        info.size_bytes += size;
+        info.file_count += 1;
+        if let Some(language) = path_language(entry.path()) {
+            let share = languages.entry(language).or_default();
+            share.0 += 1;
+            share.1 += size;
+        }
+    }
+
+    info.languages = languages
+        .into_iter()
+        .map(|(language, (files, bytes))| LanguageShare {
+            language: language.to_string(),
+            files,
+            bytes,
+        })
+        .collect();
+    info.languages.sort_by(|a, b| {
+        b.files
+            .cmp(&a.files)
+            .then(b.bytes.cmp(&a.bytes))
+            .then(a.language.cmp(&b.language))
+    });
+}
+
+/// Programming language of a source file, by extension
+pub fn path_language(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    let language = match ext.as_str() {
+        "rs" => "Rust",
+        "py" | "pyw" | "ipynb" => "Python",
+        "js" | "mjs" | "cjs" | "jsx" => "JavaScript",
+        "ts" | "tsx" => "TypeScript",
+        "go" => "Go",
+        "c" | "h" => "C",
+        "cc" | "cpp" | "cxx" | "hpp" | "hh" => "C++",
+        "cs" => "C#",
+        "java" => "Java",
+        "kt" | "kts" => "Kotlin",
+        "rb" => "Ruby",
+        "php" => "PHP",
+        "swift" => "Swift",
+        "sh" | "bash" | "zsh" | "fish" => "Shell",
+        "ps1" => "PowerShell",
+        "lua" => "Lua",
+        "sql" => "SQL",
+        "html" | "htm" => "HTML",
+        "css" | "scss" | "sass" => "CSS",
+        "md" | "markdown" => "Markdown",
+        "toml" | "yaml" | "yml" | "json" => "Config",
+        "asm" | "s" => "Assembly",
+        "sol" => "Solidity",
+        "hs" => "Haskell",
+        "ml" | "mli" => "OCaml",
+        "zig" => "Zig",
+        "nix" => "Nix",
+        _ => return None,
+    };
+    Some(language)
+}
+
+fn git_info(path: &Path) -> Option<GitInfo> {
+    let repo = Repository::discover(path).ok()?;
+    let root = repo.workdir()?.to_path_buf();
+    let repo_status = status::analyze_repo(&root).ok()?;
+
+    let (ahead, behind) = match repo_status.sync_status {
+        status::SyncStatus::Ahead(a) => (a, 0),
+        status::SyncStatus::Behind(b) => (0, b),
+        status::SyncStatus::Diverged { ahead, behind } => (ahead, behind),
+        _ => (0, 0),
+    };
+
+    Some(GitInfo {
+        root,
+        branch: repo_status.branch.clone(),
+        is_dirty: repo_status.is_dirty,
+        sync: repo_status.sync_status.display(),
+        ahead,
+        behind,
+    })
+}
+
+fn para_info(config: &Config, path: &Path) -> Option<ParaInfo> {
+    // The deepest matching folder wins, since custom configs may nest them
+    ["inbox", "projects", "areas", "resources", "archives"]
+        .iter()
+        .filter_map(|key| {
+            let root = fs::canonicalize(config.resolve_path(key)).ok()?;
+            let rel = path.strip_prefix(&root).ok()?;
+            Some((key, root.components().count(), rel.to_path_buf()))
+        })
+        .max_by_key(|(_, depth, _)| *depth)
+        .map(|(key, _, rel)| ParaInfo {
+            area: key.to_string(),
+            item: rel
+                .components()
+                .next()
+                .map(|c| c.as_os_str().to_string_lossy().to_string()),
+        })
+}
+
+/// Event membership via `.ctf_meta.json`, or via the `CTFs/<event>/...`
+/// layout that `ctf archive` and `ctf solve` leave in archives.
+fn ctf_info(config: &Config, path: &Path) -> Option<CtfInfo> {
+    let (event_root, event) = match ctf::find_event_root_from(path) {
+        Some(root) => {
+            let name = ctf::CtfMeta::load(&root)
+                .map(|m| m.name)
+                .or_else(|| file_name(&root))?;
+            (root, name)
+        }
+        None => {
+            let archived = fs::canonicalize(config.resolve_path("archives").join("CTFs")).ok()?;
+            let event = path.strip_prefix(&archived).ok()?.components().next()?;
+            let root = archived.join(event);
+            (root.clone(), file_name(&root)?)
+        }
+    };
+
+    let rel: Vec<String> = path
+        .strip_prefix(&event_root)
+        .ok()?
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+
+    let mut info = CtfInfo {
+        event,
+        category: rel.first().cloned(),
+        challenge: rel.get(1).cloned(),
+        ..Default::default()
+    };
+
+    // Challenges are event/<category>/<challenge>
+    let challenge_dirs: Vec<PathBuf> = match rel.len() {
+        0 => subdirs(&event_root)
+            .iter()
+            .flat_map(|c| subdirs(c))
+            .collect(),
+        1 => subdirs(path),
+        _ => vec![event_root.join(&rel[0]).join(&rel[1])],
+    };
+    info.challenges = challenge_dirs.len();
+    info.solved = challenge_dirs
+        .iter()
+        .filter(|c| c.join("flag.txt").is_file())
+        .count();
+    if rel.len() >= 2 {
+        info.flag = fs::read_to_string(challenge_dirs[0].join("flag.txt"))
+            .ok()
+            .map(|f| f.trim().to_string());
+    }
+
+    Some(info)
+}
+
+fn subdirs(path: &Path) -> Vec<PathBuf> {
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.path().is_dir() && !e.file_name().to_string_lossy().starts_with('.'))
+                .map(|e| e.path())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn file_name(path: &Path) -> Option<String> {
+    path.file_name().map(|n| n.to_string_lossy().to_string())
+}
+
+/// Print a `PathInfo` as aligned `Label: value` lines
+pub fn print_info(info: &PathInfo) {
+    println!("Path:      {}", info.path.display());
+    println!("Type:      {}", info.kind);
+    if let Some(magic) = &info.magic {
+        println!("Magic:     {}", magic);
+    }
+    println!(
+        "Size:      {} ({} files)",
+        output::human_size(info.size_bytes),
+        info.file_count
+    );
+    if let Some(modified) = &info.modified {
+        println!("Modified:  {}", modified);
+    }
+    if !info.languages.is_empty() {
+        let languages: Vec<String> = info
+            .languages
+            .iter()
+            .take(5)
+            .map(|l| format!("{} ({})", l.language, l.files))
+            .collect();
+        println!("Languages: {}", languages.join(", "));
+    }
+    if let Some(para) = &info.para {
+        match &para.item {
+            Some(item) => println!("PARA:      {} / {}", para.area, item),
+            None => println!("PARA:      {}", para.area),
+        }
+    }
+    if let Some(git) = &info.git {
+        println!(
+            "Git:       {} {} {} ({})",
+            git.branch.as_deref().unwrap_or("(detached)"),
+            if git.is_dirty {
+                "⚠ Dirty"
+            } else {
+                "✓ Clean"
+            },
+            git.sync,
+            git.root.display()
+        );
+    }
+    if let Some(ctf) = &info.ctf {
+        let scope: Vec<&str> = [Some(ctf.event.as_str()), ctf.category.as_deref()]
+            .into_iter()
+            .chain([ctf.challenge.as_deref()])
+            .flatten()
+            .collect();
+        println!("CTF:       {}", scope.join(" / "));
+        match &ctf.flag {
+            Some(flag) => println!("Solved:    ✓ {}", flag),
+            None => println!("Solved:    {}/{} challenges", ctf.solved, ctf.challenges),
+        }
+    }
+}
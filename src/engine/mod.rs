@@ -1,8 +1,15 @@
 pub mod auditor;
 pub mod cleaner;
 pub mod ctf;
+pub mod git;
+pub mod index;
+pub mod info;
+pub mod pcap;
+pub mod project;
+pub mod reclaim;
 pub mod scaffold;
 pub mod search;
+pub mod stale;
 pub mod stats;
 pub mod status;
 pub mod undo;
@@ -0,0 +1,697 @@
+use anyhow::{bail, Context, Result};
+use flate2::read::{DeflateDecoder, GzDecoder, ZlibDecoder};
+use std::collections::HashMap;
+use std::io::Read;
+use std::net::IpAddr;
+
+/// Link-layer types we know how to strip down to an IP packet
+const LINKTYPE_NULL: u32 = 0;
+const LINKTYPE_ETHERNET: u32 = 1;
+const LINKTYPE_RAW_OPENBSD: u32 = 12;
+const LINKTYPE_RAW: u32 = 101;
+const LINKTYPE_LINUX_SLL: u32 = 113;
+const LINKTYPE_IPV4: u32 = 228;
+const LINKTYPE_IPV6: u32 = 229;
+const LINKTYPE_LINUX_SLL2: u32 = 276;
+
+/// A reassembled payload extracted from a capture
+#[derive(Debug, Clone)]
+pub struct Stream {
+    /// Human-readable description, e.g. `tcp 10.0.0.1:51000 -> 10.0.0.2:80`
+    pub label: String,
+    pub data: Vec<u8>,
+}
+
+/// Check whether the first bytes of a file look like pcap or pcapng
+pub fn is_capture(header: &[u8]) -> bool {
+    matches!(
+        header.get(..4),
+        Some([0xd4, 0xc3, 0xb2, 0xa1])
+            | Some([0xa1, 0xb2, 0xc3, 0xd4])
+            | Some([0x4d, 0x3c, 0xb2, 0xa1])
+            | Some([0xa1, 0xb2, 0x3c, 0x4d])
+            | Some([0x0a, 0x0d, 0x0d, 0x0a])
+    )
+}
+
+/// Parse a pcap/pcapng capture and return every reassembled TCP stream and
+/// UDP flow, plus decoded HTTP bodies (de-chunked and gunzipped) as extra
+/// streams. At most `max_capture` bytes of the capture are read, and the
+/// rest is treated as truncated. Decoded bodies are capped at `max_body` bytes.
+pub fn extract_streams<R: Read>(
+    reader: R,
+    max_capture: Option<u64>,
+    max_body: Option<u64>,
+) -> Result<Vec<Stream>> {
+    let mut data = Vec::new();
+    reader
+        .take(max_capture.unwrap_or(u64::MAX))
+        .read_to_end(&mut data)
+        .context("Failed to read capture")?;
+
+    let mut flows = FlowTable::default();
+    if data.starts_with(&[0x0a, 0x0d, 0x0d, 0x0a]) {
+        parse_pcapng(&data, &mut flows)?;
+    } else {
+        parse_pcap(&data, &mut flows)?;
+    }
+
+    let mut streams = flows.into_streams();
+    let bodies: Vec<Stream> = streams
+        .iter()
+        .filter(|s| s.label.starts_with("tcp"))
+        .flat_map(|s| decode_http_bodies(s, max_body))
+        .collect();
+    streams.extend(bodies);
+    Ok(streams)
+}
+
+#[derive(Clone, Copy)]
+enum Endian {
+    Little,
+    Big,
+}
+
+impl Endian {
+    fn u16(self, b: &[u8], at: usize) -> Option<u16> {
+        let bytes: [u8; 2] = b.get(at..at + 2)?.try_into().ok()?;
+        Some(match self {
+            Endian::Little => u16::from_le_bytes(bytes),
+            Endian::Big => u16::from_be_bytes(bytes),
+        })
+    }
+
+    fn u32(self, b: &[u8], at: usize) -> Option<u32> {
+        let bytes: [u8; 4] = b.get(at..at + 4)?.try_into().ok()?;
+        Some(match self {
+            Endian::Little => u32::from_le_bytes(bytes),
+            Endian::Big => u32::from_be_bytes(bytes),
+        })
+    }
+}
+
+fn be16(b: &[u8], at: usize) -> Option<u16> {
+    Endian::Big.u16(b, at)
+}
+
+fn be32(b: &[u8], at: usize) -> Option<u32> {
+    Endian::Big.u32(b, at)
+}
+
+fn parse_pcap(data: &[u8], flows: &mut FlowTable) -> Result<()> {
+    let endian = match data.get(..4) {
+        Some([0xd4, 0xc3, 0xb2, 0xa1]) | Some([0x4d, 0x3c, 0xb2, 0xa1]) => Endian::Little,
+        Some([0xa1, 0xb2, 0xc3, 0xd4]) | Some([0xa1, 0xb2, 0x3c, 0x4d]) => Endian::Big,
+        _ => bail!("Not a pcap file"),
+    };
+    let linktype = endian.u32(data, 20).context("Truncated pcap header")?;
+
+    let mut pos = 24;
+    while let Some(incl_len) = endian.u32(data, pos + 8) {
+        let start = pos + 16;
+        let end = start + incl_len as usize;
+        let Some(frame) = data.get(start..end) else {
+            log::debug!("Truncated pcap record at offset {}", pos);
+            break;
+        };
+        flows.add_frame(linktype, frame);
+        pos = end;
+    }
+    Ok(())
+}
+
+fn parse_pcapng(data: &[u8], flows: &mut FlowTable) -> Result<()> {
+    let mut endian = Endian::Little;
+    let mut interfaces: Vec<u32> = Vec::new();
+    let mut pos = 0;
+
+    while pos + 12 <= data.len() {
+        // The section header defines byte order for everything that follows
+        if data[pos..pos + 4] == [0x0a, 0x0d, 0x0d, 0x0a] {
+            endian = match data.get(pos + 8..pos + 12) {
+                Some([0x4d, 0x3c, 0x2b, 0x1a]) => Endian::Little,
+                Some([0x1a, 0x2b, 0x3c, 0x4d]) => Endian::Big,
+                _ => bail!("Invalid pcapng byte-order magic"),
+            };
+            interfaces.clear();
+        }
+
+        let block_type = endian.u32(data, pos).unwrap_or(0);
+        let block_len = endian.u32(data, pos + 4).unwrap_or(0) as usize;
+        if block_len < 12 || pos + block_len > data.len() {
+            log::debug!("Truncated pcapng block at offset {}", pos);
+            break;
+        }
+        let block = &data[pos..pos + block_len];
+
+        match block_type {
+            // Interface Description Block
+            1 => interfaces.push(endian.u16(block, 8).unwrap_or(0) as u32),
+            // Enhanced Packet Block
+            6 => {
+                let iface = endian.u32(block, 8).unwrap_or(0) as usize;
+                let cap_len = endian.u32(block, 20).unwrap_or(0) as usize;
+                if let (Some(&linktype), Some(frame)) =
+                    (interfaces.get(iface), block.get(28..28 + cap_len))
+                {
+                    flows.add_frame(linktype, frame);
+                }
+            }
+            // Simple Packet Block (always interface 0)
+            3 => {
+                let orig_len = endian.u32(block, 8).unwrap_or(0) as usize;
+                let cap_len = orig_len.min(block_len.saturating_sub(16));
+                if let (Some(&linktype), Some(frame)) =
+                    (interfaces.first(), block.get(12..12 + cap_len))
+                {
+                    flows.add_frame(linktype, frame);
+                }
+            }
+            // Obsolete Packet Block
+            2 => {
+                let iface = endian.u16(block, 8).unwrap_or(0) as usize;
+                let cap_len = endian.u32(block, 20).unwrap_or(0) as usize;
+                if let (Some(&linktype), Some(frame)) =
+                    (interfaces.get(iface), block.get(28..28 + cap_len))
+                {
+                    flows.add_frame(linktype, frame);
+                }
+            }
+            _ => {}
+        }
+
+        pos += block_len;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Proto {
+    Tcp,
+    Udp,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct FlowKey {
+    proto: Proto,
+    src: IpAddr,
+    src_port: u16,
+    dst: IpAddr,
+    dst_port: u16,
+}
+
+#[derive(Debug, Default)]
+struct Flow {
+    /// Sequence number of the first payload byte, from the SYN if seen
+    isn: Option<u32>,
+    /// (sequence number, payload) for TCP; (arrival index, payload) for UDP
+    segments: Vec<(u32, Vec<u8>)>,
+    /// Position of the first packet, used to keep output in capture order
+    first_seen: usize,
+}
+
+#[derive(Default)]
+struct FlowTable {
+    flows: HashMap<FlowKey, Flow>,
+    packets: usize,
+}
+
+impl FlowTable {
+    fn add_frame(&mut self, linktype: u32, frame: &[u8]) {
+        self.packets += 1;
+        if let Some(ip) = strip_link_layer(linktype, frame) {
+            self.add_ip(ip);
+        }
+    }
+
+    fn add_ip(&mut self, packet: &[u8]) {
+        let Some(version) = packet.first().map(|b| b >> 4) else {
+            return;
+        };
+        let parsed = match version {
+            4 => parse_ipv4(packet),
+            6 => parse_ipv6(packet),
+            _ => None,
+        };
+        let Some((src, dst, proto, payload)) = parsed else {
+            return;
+        };
+
+        match proto {
+            6 => self.add_tcp(src, dst, payload),
+            17 => self.add_udp(src, dst, payload),
+            _ => {}
+        }
+    }
+
+    fn flow(&mut self, key: FlowKey) -> &mut Flow {
+        let order = self.packets;
+        self.flows.entry(key).or_insert_with(|| Flow {
+            first_seen: order,
+            ..Default::default()
+        })
+    }
+
+    fn add_tcp(&mut self, src: IpAddr, dst: IpAddr, segment: &[u8]) {
+        let (Some(src_port), Some(dst_port), Some(seq)) =
+            (be16(segment, 0), be16(segment, 2), be32(segment, 4))
+        else {
+            return;
+        };
+        let header_len = segment.get(12).map(|b| (b >> 4) as usize * 4).unwrap_or(0);
+        let syn = segment.get(13).map(|f| f & 0x02 != 0).unwrap_or(false);
+        let payload = segment.get(header_len..).unwrap_or_default();
+
+        let flow = self.flow(FlowKey {
+            proto: Proto::Tcp,
+            src,
+            src_port,
+            dst,
+            dst_port,
+        });
+        if syn {
+            flow.isn = Some(seq.wrapping_add(1));
+        }
+        if !payload.is_empty() {
+            flow.segments.push((seq, payload.to_vec()));
+        }
+    }
+
+    fn add_udp(&mut self, src: IpAddr, dst: IpAddr, datagram: &[u8]) {
+        let (Some(src_port), Some(dst_port)) = (be16(datagram, 0), be16(datagram, 2)) else {
+            return;
+        };
+        let payload = datagram.get(8..).unwrap_or_default();
+        if payload.is_empty() {
+            return;
+        }
+        let flow = self.flow(FlowKey {
+            proto: Proto::Udp,
+            src,
+            src_port,
+            dst,
+            dst_port,
+        });
+        let index = flow.segments.len() as u32;
+        flow.segments.push((index, payload.to_vec()));
+    }
+
+    fn into_streams(self) -> Vec<Stream> {
+        let mut flows: Vec<(FlowKey, Flow)> = self
+            .flows
+            .into_iter()
+            .filter(|(_, f)| !f.segments.is_empty())
+            .collect();
+        flows.sort_by_key(|(key, flow)| (flow.first_seen, *key));
+
+        flows
+            .into_iter()
+            .map(|(key, flow)| {
+                let proto = match key.proto {
+                    Proto::Tcp => "tcp",
+                    Proto::Udp => "udp",
+                };
+                let data = match key.proto {
+                    Proto::Tcp => reassemble_tcp(flow),
+                    Proto::Udp => flow.segments.into_iter().flat_map(|(_, d)| d).collect(),
+                };
+                Stream {
+                    label: format!(
+                        "{} {} -> {}",
+                        proto,
+                        format_endpoint(key.src, key.src_port),
+                        format_endpoint(key.dst, key.dst_port)
+                    ),
+                    data,
+                }
+            })
+            .collect()
+    }
+}
+
+fn format_endpoint(ip: IpAddr, port: u16) -> String {
+    match ip {
+        IpAddr::V4(v4) => format!("{}:{}", v4, port),
+        IpAddr::V6(v6) => format!("[{}]:{}", v6, port),
+    }
+}
+
+/// Order segments by sequence number and stitch them together, dropping
+/// retransmitted and overlapping bytes. Gaps from lost packets are left out.
+fn reassemble_tcp(flow: Flow) -> Vec<u8> {
+    let base = flow
+        .isn
+        .or_else(|| flow.segments.first().map(|(seq, _)| *seq))
+        .unwrap_or(0);
+
+    let mut segments: Vec<(u32, Vec<u8>)> = flow
+        .segments
+        .into_iter()
+        .map(|(seq, data)| (seq.wrapping_sub(base), data))
+        .collect();
+    segments.sort_by_key(|(offset, _)| *offset);
+
+    let mut stream: Vec<u8> = Vec::new();
+    let mut next: u64 = 0;
+    for (offset, data) in segments {
+        let offset = offset as u64;
+        let end = offset + data.len() as u64;
+        if end <= next {
+            continue; // pure retransmission
+        }
+        let skip = next.saturating_sub(offset) as usize;
+        stream.extend_from_slice(&data[skip..]);
+        next = end;
+    }
+    stream
+}
+
+/// Reduce a link-layer frame to its IP packet
+fn strip_link_layer(linktype: u32, frame: &[u8]) -> Option<&[u8]> {
+    match linktype {
+        LINKTYPE_ETHERNET => {
+            let mut offset = 12;
+            let mut ethertype = be16(frame, offset)?;
+            // 802.1Q / 802.1ad VLAN tags
+            while ethertype == 0x8100 || ethertype == 0x88a8 {
+                offset += 4;
+                ethertype = be16(frame, offset)?;
+            }
+            match ethertype {
+                0x0800 | 0x86dd => frame.get(offset + 2..),
+                _ => None,
+            }
+        }
+        LINKTYPE_NULL => frame.get(4..),
+        LINKTYPE_RAW | LINKTYPE_RAW_OPENBSD | LINKTYPE_IPV4 | LINKTYPE_IPV6 => Some(frame),
+        LINKTYPE_LINUX_SLL => match be16(frame, 14)? {
+            0x0800 | 0x86dd => frame.get(16..),
+            _ => None,
+        },
+        LINKTYPE_LINUX_SLL2 => match be16(frame, 0)? {
+            0x0800 | 0x86dd => frame.get(20..),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns (src, dst, protocol, payload). Non-first fragments are ignored.
+fn parse_ipv4(packet: &[u8]) -> Option<(IpAddr, IpAddr, u8, &[u8])> {
+    let header_len = (packet.first()? & 0x0f) as usize * 4;
+    let total_len = be16(packet, 2)? as usize;
+    let fragment_offset = be16(packet, 6)? & 0x1fff;
+    if fragment_offset != 0 {
+        return None;
+    }
+    let proto = *packet.get(9)?;
+    let src: [u8; 4] = packet.get(12..16)?.try_into().ok()?;
+    let dst: [u8; 4] = packet.get(16..20)?.try_into().ok()?;
+    // Ethernet padding may trail the packet; trust total length when sane
+    let end = if total_len >= header_len && total_len <= packet.len() {
+        total_len
+    } else {
+        packet.len()
+    };
+    let payload = packet.get(header_len..end)?;
+    Some((src.into(), dst.into(), proto, payload))
+}
+
+fn parse_ipv6(packet: &[u8]) -> Option<(IpAddr, IpAddr, u8, &[u8])> {
+    let payload_len = be16(packet, 4)? as usize;
+    let mut next = *packet.get(6)?;
+    let src: [u8; 16] = packet.get(8..24)?.try_into().ok()?;
+    let dst: [u8; 16] = packet.get(24..40)?.try_into().ok()?;
+    let end = (40 + payload_len).min(packet.len());
+    let mut offset = 40;
+
+    // Skip extension headers
+    loop {
+        match next {
+            0 | 43 | 60 => {
+                next = *packet.get(offset)?;
+                offset += (*packet.get(offset + 1)? as usize + 1) * 8;
+            }
+            44 => {
+                next = *packet.get(offset)?;
+                offset += 8;
+            }
+            _ => break,
+        }
+    }
+
+    let payload = packet.get(offset..end)?;
+    Some((src.into(), dst.into(), next, payload))
+}
+
+/// Walk the HTTP/1.x messages in a TCP stream and return the bodies that
+/// needed decoding (chunked transfer or gzip/deflate content encoding).
+fn decode_http_bodies(stream: &Stream, max_body: Option<u64>) -> Vec<Stream> {
+    let mut bodies = Vec::new();
+    let data = &stream.data;
+    let mut pos = 0;
+    let mut index = 0;
+
+    while pos < data.len() && looks_like_http(&data[pos..]) {
+        let Some(header_end) = find(&data[pos..], b"\r\n\r\n").map(|i| pos + i + 4) else {
+            break;
+        };
+        let head = String::from_utf8_lossy(&data[pos..header_end]);
+        let is_request = !head.starts_with("HTTP/");
+        let header = |name: &str| {
+            head.lines().skip(1).find_map(|line| {
+                let (key, value) = line.split_once(':')?;
+                key.trim()
+                    .eq_ignore_ascii_case(name)
+                    .then(|| value.trim().to_ascii_lowercase())
+            })
+        };
+
+        let chunked = header("transfer-encoding").is_some_and(|v| v.contains("chunked"));
+        let encoding = header("content-encoding");
+        let content_length = header("content-length").and_then(|v| v.parse::<usize>().ok());
+
+        let rest = &data[header_end..];
+        let (raw_body, consumed) = if chunked {
+            dechunk(rest)
+        } else if let Some(len) = content_length {
+            let len = len.min(rest.len());
+            (rest[..len].to_vec(), len)
+        } else if is_request {
+            (Vec::new(), 0)
+        } else {
+            (rest.to_vec(), rest.len())
+        };
+        pos = header_end + consumed;
+        index += 1;
+
+        let decoded = match encoding.as_deref() {
+            Some(enc) if enc.contains("gzip") => {
+                inflate(GzDecoder::new(raw_body.as_slice()), max_body)
+            }
+            Some(enc) if enc.contains("deflate") => {
+                inflate(ZlibDecoder::new(raw_body.as_slice()), max_body)
+                    .or_else(|| inflate(DeflateDecoder::new(raw_body.as_slice()), max_body))
+            }
+            _ if chunked => Some(raw_body),
+            _ => None,
+        };
+
+        if let Some(body) = decoded.filter(|b| !b.is_empty()) {
+            bodies.push(Stream {
+                label: format!("http body #{} ({})", index, stream.label),
+                data: body,
+            });
+        }
+
+        if consumed == 0 && !is_request {
+            break;
+        }
+    }
+
+    bodies
+}
+
+fn looks_like_http(data: &[u8]) -> bool {
+    const STARTS: [&[u8]; 10] = [
+        b"HTTP/1.",
+        b"GET ",
+        b"POST ",
+        b"PUT ",
+        b"HEAD ",
+        b"DELETE ",
+        b"PATCH ",
+        b"OPTIONS ",
+        b"CONNECT ",
+        b"TRACE ",
+    ];
+    STARTS.iter().any(|s| data.starts_with(s))
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+/// Decode a chunked body. Returns the body and the number of bytes consumed.
+/// A malformed chunk size ends the body; a chunk cut off by the end of the
+/// capture is kept as far as it goes.
+fn dechunk(data: &[u8]) -> (Vec<u8>, usize) {
+    let mut body = Vec::new();
+    let mut pos = 0;
+    while let Some(line_end) = find(&data[pos..], b"\r\n").map(|i| pos + i) {
+        let size_str = String::from_utf8_lossy(&data[pos..line_end]);
+        let size_str = size_str.split(';').next().unwrap_or("").trim();
+        let Ok(size) = usize::from_str_radix(size_str, 16) else {
+            break;
+        };
+        let start = line_end + 2;
+        if size == 0 {
+            // Skip trailers up to the final empty line
+            pos = find(&data[start..], b"\r\n")
+                .map(|i| start + i + 2)
+                .unwrap_or(data.len());
+            break;
+        }
+        let Some(end) = start.checked_add(size) else {
+            break;
+        };
+        let end = end.min(data.len());
+        body.extend_from_slice(&data[start..end]);
+        pos = (end + 2).min(data.len());
+    }
+    (body, pos)
+}
+
+fn inflate<R: Read>(decoder: R, max_body: Option<u64>) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    let result = match max_body {
+        Some(max) => decoder.take(max).read_to_end(&mut out),
+        None => {
+            let mut decoder = decoder;
+            decoder.read_to_end(&mut out)
+        }
+    };
+    // Keep whatever was decoded from truncated captures
+    match result {
+        Ok(_) => Some(out),
+        Err(_) if !out.is_empty() => Some(out),
+        Err(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Ethernet/IPv4/TCP frame from 10.0.0.1:1234 to 10.0.0.2:80
+    fn tcp_frame(seq: u32, flags: u8, payload: &[u8]) -> Vec<u8> {
+        let mut frame = vec![0u8; 12];
+        frame.extend_from_slice(&[0x08, 0x00]);
+        frame.extend_from_slice(&[0x45, 0]);
+        frame.extend_from_slice(&((40 + payload.len()) as u16).to_be_bytes());
+        frame.extend_from_slice(&[0, 0, 0, 0, 64, 6, 0, 0, 10, 0, 0, 1, 10, 0, 0, 2]);
+        frame.extend_from_slice(&1234u16.to_be_bytes());
+        frame.extend_from_slice(&80u16.to_be_bytes());
+        frame.extend_from_slice(&seq.to_be_bytes());
+        frame.extend_from_slice(&[0, 0, 0, 0, 0x50, flags, 0xff, 0xff, 0, 0, 0, 0]);
+        frame.extend_from_slice(payload);
+        frame
+    }
+
+    /// Little-endian pcap with an Ethernet link type
+    fn pcap(frames: &[Vec<u8>]) -> Vec<u8> {
+        let mut data = vec![0xd4, 0xc3, 0xb2, 0xa1, 2, 0, 4, 0];
+        data.extend_from_slice(&[0; 8]);
+        data.extend_from_slice(&65535u32.to_le_bytes());
+        data.extend_from_slice(&LINKTYPE_ETHERNET.to_le_bytes());
+        for frame in frames {
+            data.extend_from_slice(&[0; 8]);
+            data.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+            data.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+            data.extend_from_slice(frame);
+        }
+        data
+    }
+
+    #[test]
+    fn test_dechunk() {
+        let (body, consumed) = dechunk(b"5\r\nhello\r\n6;ext=1\r\n world\r\n0\r\n\r\nNEXT");
+        assert_eq!(body, b"hello world");
+        assert_eq!(consumed, 32);
+    }
+
+    #[test]
+    fn test_dechunk_truncated_chunk_keeps_partial_data() {
+        let (body, consumed) = dechunk(b"a\r\nshort");
+        assert_eq!(body, b"short");
+        assert_eq!(consumed, 8);
+    }
+
+    #[test]
+    fn test_dechunk_malformed_size_ends_body() {
+        let (body, _) = dechunk(b"3\r\nabc\r\nffffffffffffffff\r\nxyz\r\n");
+        assert_eq!(body, b"abc");
+
+        let (body, _) = dechunk(b"3\r\nabc\r\nzz\r\nxyz\r\n");
+        assert_eq!(body, b"abc");
+    }
+
+    #[test]
+    fn test_reassemble_tcp_orders_segments_and_drops_retransmits() {
+        let data = pcap(&[
+            tcp_frame(99, 0x02, b""),
+            tcp_frame(106, 0x18, b"world"),
+            tcp_frame(100, 0x18, b"hello "),
+            tcp_frame(100, 0x18, b"hello "),
+            tcp_frame(103, 0x18, b"lo wo"),
+        ]);
+        let streams = extract_streams(data.as_slice(), None, None).unwrap();
+        assert_eq!(streams.len(), 1);
+        assert_eq!(streams[0].label, "tcp 10.0.0.1:1234 -> 10.0.0.2:80");
+        assert_eq!(streams[0].data, b"hello world");
+    }
+
+    #[test]
+    fn test_truncated_pcap_keeps_complete_records() {
+        let mut data = pcap(&[tcp_frame(1, 0x18, b"first"), tcp_frame(6, 0x18, b"second")]);
+        data.truncate(data.len() - 3);
+        let streams = extract_streams(data.as_slice(), None, None).unwrap();
+        assert_eq!(streams[0].data, b"first");
+
+        // Capping the read behaves the same as a truncated file
+        let data = pcap(&[tcp_frame(1, 0x18, b"first"), tcp_frame(6, 0x18, b"second")]);
+        let cap = data.len() as u64 - 3;
+        let streams = extract_streams(data.as_slice(), Some(cap), None).unwrap();
+        assert_eq!(streams[0].data, b"first");
+    }
+
+    #[test]
+    fn test_malformed_captures() {
+        assert!(extract_streams(&b"\xd4\xc3\xb2\xa1\x02\x00"[..], None, None).is_err());
+        assert!(extract_streams(&b"not a capture"[..], None, None).is_err());
+
+        // pcapng section header with a bogus byte-order magic
+        let mut ng = vec![0x0a, 0x0d, 0x0d, 0x0a, 28, 0, 0, 0, 1, 2, 3, 4];
+        ng.extend_from_slice(&[0; 16]);
+        assert!(extract_streams(ng.as_slice(), None, None).is_err());
+
+        // Block length pointing past the end of the file
+        let mut ng = vec![
+            0x0a, 0x0d, 0x0d, 0x0a, 0xff, 0xff, 0, 0, 0x4d, 0x3c, 0x2b, 0x1a,
+        ];
+        ng.extend_from_slice(&[0; 16]);
+        assert!(extract_streams(ng.as_slice(), None, None)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_http_body_with_overflowing_chunk_size() {
+        let response = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n\
+            4\r\nflag\r\nffffffffffffffff\r\n{x}\r\n";
+        let data = pcap(&[tcp_frame(1, 0x18, response)]);
+        let streams = extract_streams(data.as_slice(), None, None).unwrap();
+        assert_eq!(streams.len(), 2);
+        assert_eq!(streams[1].data, b"flag");
+    }
+}
@@ -0,0 +1,466 @@
+use crate::config::Config;
+use crate::engine::{status, undo};
+use anyhow::{Context, Result};
+use fs_err as fs;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+
+/// Manifest file names, in lookup order. New manifests are written as TOML.
+pub const MANIFEST_TOML: &str = ".wardex.toml";
+pub const MANIFEST_JSON: &str = ".wardex.json";
+
+/// PARA folders a project can live in while it is not archived
+pub const LIFECYCLE_KEYS: &[&str] = &["projects", "areas", "resources"];
+
+/// Lifecycle of a project
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectStatus {
+    Active,
+    Paused,
+    Done,
+}
+
+impl fmt::Display for ProjectStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            ProjectStatus::Active => "active",
+            ProjectStatus::Paused => "paused",
+            ProjectStatus::Done => "done",
+        };
+        f.write_str(s)
+    }
+}
+
+impl FromStr for ProjectStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "active" => Ok(ProjectStatus::Active),
+            "paused" => Ok(ProjectStatus::Paused),
+            "done" => Ok(ProjectStatus::Done),
+            _ => anyhow::bail!("Unknown status '{}'. Use active, paused or done", s),
+        }
+    }
+}
+
+/// Per-project metadata stored in `.wardex.toml` (or `.wardex.json`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProjectManifest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ProjectStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Named links, e.g. `repo = "https://..."`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub links: BTreeMap<String, String>,
+    /// File name of the packed project, set only on compressed archive stubs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive: Option<String>,
+}
+
+impl ProjectManifest {
+    /// Load the manifest of a project directory, if it has one
+    pub fn load(dir: &Path) -> Result<Option<Self>> {
+        let toml_path = dir.join(MANIFEST_TOML);
+        if toml_path.exists() {
+            let content = fs::read_to_string(&toml_path)?;
+            let manifest = toml::from_str(&content)
+                .with_context(|| format!("Invalid manifest {}", toml_path.display()))?;
+            return Ok(Some(manifest));
+        }
+
+        let json_path = dir.join(MANIFEST_JSON);
+        if json_path.exists() {
+            let content = fs::read_to_string(&json_path)?;
+            let manifest = serde_json::from_str(&content)
+                .with_context(|| format!("Invalid manifest {}", json_path.display()))?;
+            return Ok(Some(manifest));
+        }
+
+        Ok(None)
+    }
+
+    /// Save next to an existing `.wardex.json`, otherwise as `.wardex.toml`
+    pub fn save(&self, dir: &Path) -> Result<PathBuf> {
+        let json_path = dir.join(MANIFEST_JSON);
+        if json_path.exists() && !dir.join(MANIFEST_TOML).exists() {
+            fs::write(&json_path, serde_json::to_string_pretty(self)? + "\n")?;
+            return Ok(json_path);
+        }
+
+        let toml_path = dir.join(MANIFEST_TOML);
+        fs::write(&toml_path, toml::to_string_pretty(self)?)?;
+        Ok(toml_path)
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+}
+
+/// Restricts projects by manifest fields. An empty filter matches everything,
+/// including projects without a manifest.
+#[derive(Debug, Clone, Default)]
+pub struct ProjectFilter {
+    /// All of these tags must be present
+    pub tags: Vec<String>,
+    pub status: Option<ProjectStatus>,
+    pub language: Option<String>,
+}
+
+impl ProjectFilter {
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty() && self.status.is_none() && self.language.is_none()
+    }
+
+    pub fn matches(&self, manifest: Option<&ProjectManifest>) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        let Some(m) = manifest else {
+            return false;
+        };
+        self.tags.iter().all(|t| m.has_tag(t))
+            && self.status.is_none_or(|s| m.status == Some(s))
+            && self.language.as_ref().is_none_or(|lang| {
+                m.language
+                    .as_ref()
+                    .is_some_and(|l| l.eq_ignore_ascii_case(lang))
+            })
+    }
+}
+
+/// A top-level project directory and its manifest
+#[derive(Debug, Clone)]
+pub struct ProjectInfo {
+    pub name: String,
+    pub path: PathBuf,
+    pub manifest: Option<ProjectManifest>,
+}
+
+/// Top-level directories of the projects folder, sorted by name
+pub fn list_projects(config: &Config) -> Result<Vec<ProjectInfo>> {
+    let projects_dir = config.resolve_path("projects");
+    let mut projects = Vec::new();
+    if !projects_dir.exists() {
+        return Ok(projects);
+    }
+
+    for entry in fs::read_dir(&projects_dir)?.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !path.is_dir() || name.starts_with('.') {
+            continue;
+        }
+        projects.push(ProjectInfo {
+            name,
+            manifest: ProjectManifest::load(&path).ok().flatten(),
+            path,
+        });
+    }
+
+    projects.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(projects)
+}
+
+/// The project a path belongs to: the nearest ancestor with a manifest,
+/// else the nearest git repository, else the path itself.
+pub fn project_root(start: &Path) -> Result<PathBuf> {
+    let start = fs::canonicalize(start)?;
+    let marked = |markers: &[&str]| {
+        start
+            .ancestors()
+            .find(|dir| markers.iter().any(|m| dir.join(m).exists()))
+            .map(Path::to_path_buf)
+    };
+    Ok(marked(&[MANIFEST_TOML, MANIFEST_JSON])
+        .or_else(|| marked(&[".git"]))
+        .unwrap_or(start))
+}
+
+/// Add or remove tags on the project containing `path`
+pub fn tag_project(path: &Path, tags: &[String], remove: bool) -> Result<ProjectManifest> {
+    let root = project_root(path)?;
+    let mut manifest = ProjectManifest::load(&root)?.unwrap_or_default();
+
+    for tag in tags {
+        if remove {
+            manifest.tags.retain(|t| !t.eq_ignore_ascii_case(tag));
+        } else if !manifest.has_tag(tag) {
+            manifest.tags.push(tag.clone());
+        }
+    }
+
+    manifest.save(&root)?;
+    Ok(manifest)
+}
+
+/// Set a manifest field on the project containing `path`.
+///
+/// Keys: `description`, `status`, `language`, `link.<name>`. An empty value
+/// clears the field.
+pub fn set_field(path: &Path, key: &str, value: &str) -> Result<ProjectManifest> {
+    let root = project_root(path)?;
+    let mut manifest = ProjectManifest::load(&root)?.unwrap_or_default();
+    let value = value.trim();
+    let optional = || (!value.is_empty()).then(|| value.to_string());
+
+    match key {
+        "description" => manifest.description = optional(),
+        "language" => manifest.language = optional(),
+        "status" => {
+            manifest.status = if value.is_empty() {
+                None
+            } else {
+                Some(value.parse()?)
+            }
+        }
+        _ => match key.strip_prefix("link.") {
+            Some(name) if !name.is_empty() => {
+                if value.is_empty() {
+                    manifest.links.remove(name);
+                } else {
+                    manifest.links.insert(name.to_string(), value.to_string());
+                }
+            }
+            _ => anyhow::bail!(
+                "Unknown field '{}'. Use description, status, language or link.<name>",
+                key
+            ),
+        },
+    }
+
+    manifest.save(&root)?;
+    Ok(manifest)
+}
+
+/// Where a lifecycle command moved a project
+#[derive(Debug, Clone)]
+pub struct ProjectMove {
+    pub source: PathBuf,
+    pub destination: PathBuf,
+    pub compressed: bool,
+}
+
+/// Move a project from projects to archives, optionally packing it into a
+/// `.tar.zst` next to a stub manifest. Repositories with uncommitted work or
+/// commits and branches not on a remote are refused unless `force` is set.
+pub fn archive_project(
+    config: &Config,
+    name: &str,
+    compress: bool,
+    force: bool,
+) -> Result<ProjectMove> {
+    let source = find_project_dir(config, &["projects"], name)?;
+    let destination = config.resolve_path("archives").join(name);
+    if destination.exists() {
+        anyhow::bail!("{:?} already exists in archives", name);
+    }
+    if !force {
+        ensure_work_is_saved(&source)?;
+    }
+
+    fs::create_dir_all(config.resolve_path("archives"))?;
+    if compress {
+        compress_project(&source, &destination)?;
+        undo::log_op(config, undo::OpType::Compress, &source, &destination)?;
+    } else {
+        fs::rename(&source, &destination)?;
+        undo::log_move(config, &source, &destination)?;
+    }
+
+    Ok(ProjectMove {
+        source,
+        destination,
+        compressed: compress,
+    })
+}
+
+/// Bring an archived project back into projects, unpacking it if needed
+pub fn restore_project(config: &Config, name: &str) -> Result<ProjectMove> {
+    let source = find_project_dir(config, &["archives"], name)?;
+    let destination = config.resolve_path("projects").join(name);
+    if destination.exists() {
+        anyhow::bail!("{:?} already exists in projects", name);
+    }
+
+    let compressed = ProjectManifest::load(&source)?.is_some_and(|m| m.archive.is_some());
+    fs::create_dir_all(config.resolve_path("projects"))?;
+    if compressed {
+        extract_project(&source, &destination)?;
+        undo::log_op(config, undo::OpType::Extract, &source, &destination)?;
+    } else {
+        fs::rename(&source, &destination)?;
+        undo::log_move(config, &source, &destination)?;
+    }
+
+    Ok(ProjectMove {
+        source,
+        destination,
+        compressed,
+    })
+}
+
+/// Move a project between projects, areas and resources
+pub fn move_project(config: &Config, name: &str, to: &str) -> Result<ProjectMove> {
+    if !LIFECYCLE_KEYS.contains(&to) {
+        anyhow::bail!(
+            "Cannot move to '{}'. Use {} (or `project archive`)",
+            to,
+            LIFECYCLE_KEYS.join(", ")
+        );
+    }
+    let others: Vec<&str> = LIFECYCLE_KEYS
+        .iter()
+        .copied()
+        .filter(|k| *k != to)
+        .collect();
+    let source = find_project_dir(config, &others, name)?;
+    let destination = config.resolve_path(to).join(name);
+    if destination.exists() {
+        anyhow::bail!("{:?} already exists in {}", name, to);
+    }
+
+    fs::create_dir_all(config.resolve_path(to))?;
+    fs::rename(&source, &destination)?;
+    undo::log_move(config, &source, &destination)?;
+
+    Ok(ProjectMove {
+        source,
+        destination,
+        compressed: false,
+    })
+}
+
+/// Pack `source` into `stub_dir/<name>.tar.zst`, write a stub manifest
+/// pointing at it and remove `source`.
+pub fn compress_project(source: &Path, stub_dir: &Path) -> Result<()> {
+    let name = source
+        .file_name()
+        .context("Project path has no name")?
+        .to_string_lossy()
+        .to_string();
+    let archive_name = format!("{}.tar.zst", name);
+
+    fs::create_dir_all(stub_dir)?;
+    let packed = (|| -> Result<()> {
+        let file = fs::File::create(stub_dir.join(&archive_name))?;
+        let mut builder = tar::Builder::new(zstd::Encoder::new(file, 0)?);
+        builder.follow_symlinks(false);
+        builder.append_dir_all(".", source)?;
+        builder.into_inner()?.finish()?;
+        Ok(())
+    })();
+    if let Err(e) = packed {
+        let _ = fs::remove_dir_all(stub_dir);
+        return Err(e.context(format!("Failed to compress {:?}", source)));
+    }
+
+    let mut stub = ProjectManifest::load(source)?.unwrap_or_default();
+    stub.archive = Some(archive_name);
+    stub.save(stub_dir)?;
+
+    fs::remove_dir_all(source)?;
+    Ok(())
+}
+
+/// Unpack the archive referenced by the stub manifest in `stub_dir` into
+/// `destination` and remove the stub.
+pub fn extract_project(stub_dir: &Path, destination: &Path) -> Result<()> {
+    let archive_name = ProjectManifest::load(stub_dir)?
+        .and_then(|m| m.archive)
+        .with_context(|| format!("{:?} is not a compressed archive", stub_dir))?;
+    if destination.exists() {
+        anyhow::bail!("{:?} already exists", destination);
+    }
+
+    let file = fs::File::open(stub_dir.join(&archive_name))?;
+    let mut archive = tar::Archive::new(zstd::Decoder::new(file)?);
+    archive.set_preserve_permissions(true);
+    if let Err(e) = archive.unpack(destination) {
+        let _ = fs::remove_dir_all(destination);
+        return Err(anyhow::Error::new(e).context(format!("Failed to extract {}", archive_name)));
+    }
+
+    fs::remove_dir_all(stub_dir)?;
+    Ok(())
+}
+
+fn find_project_dir(config: &Config, keys: &[&str], name: &str) -> Result<PathBuf> {
+    // A bare directory name, so neither the lookup nor the destination built
+    // from it can escape the PARA folders
+    let mut components = Path::new(name).components();
+    if !matches!(
+        (components.next(), components.next()),
+        (Some(Component::Normal(_)), None)
+    ) {
+        anyhow::bail!("Invalid project name {:?}: expected a folder name", name);
+    }
+
+    keys.iter()
+        .map(|key| config.resolve_path(key).join(name))
+        .find(|path| path.is_dir())
+        .with_context(|| format!("Project '{}' not found in {}", name, keys.join(", ")))
+}
+
+/// Refuse to archive a repository whose work only exists locally
+fn ensure_work_is_saved(path: &Path) -> Result<()> {
+    if !path.join(".git").exists() {
+        return Ok(());
+    }
+    let repo = status::analyze_repo(path)?;
+    if repo.is_dirty {
+        anyhow::bail!(
+            "{:?} has uncommitted changes. Commit them or pass --force",
+            repo.name
+        );
+    }
+    if repo.has_unpushed() {
+        anyhow::bail!(
+            "{:?} has unpushed commits ({}). Push them or pass --force",
+            repo.name,
+            repo.sync_status.display()
+        );
+    }
+    if repo.has_local_only_work() {
+        anyhow::bail!(
+            "{:?} has {} branch(es) without an upstream. Push them or pass --force",
+            repo.name,
+            repo.branches_without_upstream
+        );
+    }
+    Ok(())
+}
+
+/// Print the manifest fields of the project at `root`
+pub fn print_manifest(root: &Path, m: &ProjectManifest) {
+    let or_dash = |v: Option<String>| v.unwrap_or_else(|| "-".to_string());
+    println!("Project:     {}", root.display());
+    println!("Description: {}", or_dash(m.description.clone()));
+    println!("Status:      {}", or_dash(m.status.map(|s| s.to_string())));
+    println!("Language:    {}", or_dash(m.language.clone()));
+    println!(
+        "Tags:        {}",
+        if m.tags.is_empty() {
+            "-".to_string()
+        } else {
+            m.tags.join(", ")
+        }
+    );
+    if let Some(archive) = &m.archive {
+        println!("Archive:     {}", archive);
+    }
+    for (name, url) in &m.links {
+        println!("Link:        {} = {}", name, url);
+    }
+}
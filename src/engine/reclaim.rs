@@ -0,0 +1,338 @@
+use crate::config::Config;
+use crate::engine::stale;
+use crate::utils::fs as wx_fs;
+use crate::utils::output;
+use anyhow::Result;
+use ignore::WalkBuilder;
+use rayon::prelude::*;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Project ecosystem, detected from its manifest file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectKind {
+    Rust,
+    Node,
+    Python,
+}
+
+impl ProjectKind {
+    const ALL: [ProjectKind; 3] = [ProjectKind::Rust, ProjectKind::Node, ProjectKind::Python];
+
+    fn markers(&self) -> &'static [&'static str] {
+        match self {
+            ProjectKind::Rust => &["Cargo.toml"],
+            ProjectKind::Node => &["package.json"],
+            ProjectKind::Python => &["pyproject.toml", "setup.py", "requirements.txt"],
+        }
+    }
+
+    /// Directories directly in the project root that can be regenerated
+    fn artifact_dirs(&self) -> &'static [&'static str] {
+        match self {
+            ProjectKind::Rust => &["target"],
+            ProjectKind::Node => &["node_modules", ".next"],
+            ProjectKind::Python => &[".venv", "venv", ".tox", ".pytest_cache", ".mypy_cache"],
+        }
+    }
+}
+
+impl std::fmt::Display for ProjectKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProjectKind::Rust => write!(f, "rust"),
+            ProjectKind::Node => write!(f, "node"),
+            ProjectKind::Python => write!(f, "python"),
+        }
+    }
+}
+
+/// Python bytecode caches, collected anywhere below a Python project
+const PYCACHE: &str = "__pycache__";
+
+/// Build output or dependency cache of one project
+#[derive(Debug, Clone, Serialize)]
+pub struct Artifact {
+    /// Project root, relative to the workspace
+    pub project: PathBuf,
+    pub kind: ProjectKind,
+    /// Directory name, e.g. "target" or "__pycache__"
+    pub name: String,
+    /// Every directory of this artifact (several for `__pycache__`)
+    pub paths: Vec<PathBuf>,
+    pub size_bytes: u64,
+    /// Unix timestamp of the newest entry inside the artifact
+    pub last_used: Option<i64>,
+    /// Days since the project's last commit or source change
+    pub project_idle_days: i64,
+}
+
+impl Artifact {
+    pub fn to_row(&self) -> ArtifactRow<'_> {
+        ArtifactRow {
+            project: &self.project,
+            kind: self.kind,
+            name: &self.name,
+            paths: self
+                .paths
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join("; "),
+            size_bytes: self.size_bytes,
+            last_used: self.last_used,
+            project_idle_days: self.project_idle_days,
+        }
+    }
+}
+
+/// An artifact flattened for CSV output
+#[derive(Debug, Serialize)]
+pub struct ArtifactRow<'a> {
+    pub project: &'a Path,
+    pub kind: ProjectKind,
+    pub name: &'a str,
+    /// Semicolon-separated
+    pub paths: String,
+    pub size_bytes: u64,
+    pub last_used: Option<i64>,
+    pub project_idle_days: i64,
+}
+
+#[derive(Debug, Default)]
+pub struct ReclaimReport {
+    /// Largest first
+    pub artifacts: Vec<Artifact>,
+    pub workspace_not_found: bool,
+}
+
+impl ReclaimReport {
+    pub fn total_bytes(&self) -> u64 {
+        self.artifacts.iter().map(|a| a.size_bytes).sum()
+    }
+
+    /// Artifacts of projects idle for at least `days`
+    pub fn reclaimable(&self, days: i64) -> impl Iterator<Item = &Artifact> {
+        self.artifacts
+            .iter()
+            .filter(move |a| a.project_idle_days >= days)
+    }
+}
+
+/// What happened to one artifact directory during cleaning
+#[derive(Debug, Clone)]
+pub struct CleanedArtifact {
+    pub path: PathBuf,
+    pub error: Option<String>,
+}
+
+/// Find artifact directories in every Rust, Node and Python project in the
+/// workspace. Artifacts are never descended into, so nested projects inside
+/// `node_modules` or a virtualenv are not reported.
+pub fn find_artifacts(config: &Config) -> Result<ReclaimReport> {
+    let workspace = config.resolve_path("workspace");
+    if !workspace.exists() {
+        return Ok(ReclaimReport {
+            workspace_not_found: true,
+            ..Default::default()
+        });
+    }
+
+    // (project root, kind, artifact name) -> directories
+    let mut found: HashMap<(PathBuf, ProjectKind, String), Vec<PathBuf>> = HashMap::new();
+    let mut python_roots: Vec<PathBuf> = Vec::new();
+
+    // Artifacts are gitignored and often hidden, so no standard filters
+    let walker = WalkBuilder::new(&workspace)
+        .standard_filters(false)
+        .follow_links(false)
+        .filter_entry(|e| !is_artifact_name(&e.file_name().to_string_lossy()))
+        .build();
+    for entry in walker.filter_map(|e| e.ok()) {
+        if !entry.file_type().is_some_and(|ft| ft.is_dir()) {
+            continue;
+        }
+        let dir = entry.path();
+
+        for kind in ProjectKind::ALL {
+            if !kind.markers().iter().any(|m| dir.join(m).is_file()) {
+                continue;
+            }
+            if kind == ProjectKind::Python {
+                python_roots.push(dir.to_path_buf());
+            }
+            for name in kind.artifact_dirs() {
+                let artifact = dir.join(name);
+                if artifact.is_dir() {
+                    found
+                        .entry((dir.to_path_buf(), kind, name.to_string()))
+                        .or_default()
+                        .push(artifact);
+                }
+            }
+        }
+
+        let pycache = dir.join(PYCACHE);
+        if pycache.is_dir() {
+            // Attributed to the innermost enclosing Python project
+            if let Some(root) = python_roots
+                .iter()
+                .filter(|r| dir.starts_with(r))
+                .max_by_key(|r| r.components().count())
+            {
+                found
+                    .entry((root.clone(), ProjectKind::Python, PYCACHE.to_string()))
+                    .or_default()
+                    .push(pycache);
+            }
+        }
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let mut idle_days: HashMap<PathBuf, i64> = HashMap::new();
+    for (root, _, _) in found.keys() {
+        idle_days.entry(root.clone()).or_insert_with(|| {
+            let last_activity = project_activity(root).unwrap_or(0);
+            (now - last_activity).max(0) / 86400
+        });
+    }
+
+    let mut artifacts: Vec<Artifact> = found
+        .into_par_iter()
+        .map(|((root, kind, name), paths)| {
+            let (size_bytes, last_used) = paths
+                .iter()
+                .map(|p| dir_usage(p))
+                .fold((0, None), |(size, newest), (s, n)| {
+                    (size + s, newest.max(n))
+                });
+            Artifact {
+                project: root.strip_prefix(&workspace).unwrap_or(&root).to_path_buf(),
+                kind,
+                name,
+                paths,
+                size_bytes,
+                last_used,
+                project_idle_days: idle_days[&root],
+            }
+        })
+        .collect();
+
+    artifacts.sort_by(|a, b| {
+        b.size_bytes
+            .cmp(&a.size_bytes)
+            .then_with(|| a.project.cmp(&b.project))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(ReclaimReport {
+        artifacts,
+        workspace_not_found: false,
+    })
+}
+
+/// Move the artifact directories to the trash, or delete them outright
+/// (what `cargo clean` or `rm -rf node_modules` would do). Failures are
+/// reported per directory rather than aborting the run.
+pub fn clean_artifacts(
+    artifacts: &[&Artifact],
+    delete: bool,
+    dry_run: bool,
+) -> Vec<CleanedArtifact> {
+    artifacts
+        .iter()
+        .flat_map(|a| &a.paths)
+        .map(|path| {
+            let result = if dry_run {
+                Ok(())
+            } else if delete {
+                fs_err::remove_dir_all(path).map_err(anyhow::Error::from)
+            } else {
+                wx_fs::move_to_trash(path).map(|_| ())
+            };
+            CleanedArtifact {
+                path: path.clone(),
+                error: result.err().map(|e| e.to_string()),
+            }
+        })
+        .collect()
+}
+
+fn is_artifact_name(name: &str) -> bool {
+    name == ".git"
+        || name == PYCACHE
+        || ProjectKind::ALL
+            .iter()
+            .any(|k| k.artifact_dirs().contains(&name))
+}
+
+/// Later of the last commit and the newest file outside artifact directories
+fn project_activity(root: &Path) -> Option<i64> {
+    let newest_source = WalkBuilder::new(root)
+        .standard_filters(false)
+        .filter_entry(|e| !is_artifact_name(&e.file_name().to_string_lossy()))
+        .build()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| mtime(&e.metadata().ok()?))
+        .max();
+    stale::last_commit_time(root).max(newest_source)
+}
+
+/// Total size and newest mtime of everything below `path`
+fn dir_usage(path: &Path) -> (u64, Option<i64>) {
+    WalkBuilder::new(path)
+        .standard_filters(false)
+        .follow_links(false)
+        .build()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.metadata().ok())
+        .fold((0, None), |(size, newest), meta| {
+            let size = if meta.is_file() {
+                size + meta.len()
+            } else {
+                size
+            };
+            (size, newest.max(mtime(&meta)))
+        })
+}
+
+fn mtime(meta: &std::fs::Metadata) -> Option<i64> {
+    let modified = meta.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_secs() as i64)
+}
+
+/// Print artifacts as a table, marking those of projects idle for `days`
+pub fn print_artifacts(artifacts: &[Artifact], days: i64) {
+    println!(
+        "\n{:<30} {:<7} {:<14} {:>10} {:>9} {:>6}",
+        "Project", "Kind", "Artifact", "Size", "Last used", "Idle"
+    );
+    println!("{}", "-".repeat(81));
+    for a in artifacts {
+        let last_used = a
+            .last_used
+            .map(output::format_age)
+            .unwrap_or_else(|| "-".to_string());
+        let name = if a.paths.len() > 1 {
+            format!("{} ({})", a.name, a.paths.len())
+        } else {
+            a.name.clone()
+        };
+        println!(
+            "{:<30} {:<7} {:<14} {:>10} {:>9} {:>5}d{}",
+            output::truncate(&a.project.display().to_string(), 30),
+            a.kind.to_string(),
+            name,
+            output::human_size(a.size_bytes),
+            last_used,
+            a.project_idle_days,
+            if a.project_idle_days >= days {
+                " *"
+            } else {
+                ""
+            }
+        );
+    }
+}
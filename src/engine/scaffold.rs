@@ -1,4 +1,5 @@
 use crate::config::Config;
+use crate::engine::project::{ProjectManifest, ProjectStatus};
 use anyhow::Result;
 use fs_err as fs;
 use std::process::Command;
@@ -27,6 +28,19 @@ pub fn init_project(config: &Config, name: &str, project_type: &str) -> Result<(
         }
     }
 
+    let language = match project_type {
+        "rust" | "python" => Some(project_type.to_string()),
+        "node" | "js" => Some("javascript".to_string()),
+        "ts" => Some("typescript".to_string()),
+        _ => None,
+    };
+    let manifest = ProjectManifest {
+        status: Some(ProjectStatus::Active),
+        language,
+        ..Default::default()
+    };
+    manifest.save(&project_path)?;
+
     // Initialize git
     if Command::new("git")
         .arg("init")
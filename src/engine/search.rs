@@ -1,40 +1,69 @@
-use crate::config::Config;
+use crate::config::{Config, SearchConfig};
+use crate::core::history::JumpHistory;
+use crate::engine::project::{ProjectFilter, ProjectManifest};
+use crate::engine::{ctf, index, pcap};
 use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
 use fs_err::File;
 use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
-use grep_regex::RegexMatcher;
-use grep_searcher::sinks::UTF8;
-use grep_searcher::{BinaryDetection, SearcherBuilder};
-use ignore::WalkBuilder;
-use std::io::Read;
+use grep_matcher::Matcher;
+use grep_regex::{RegexMatcher, RegexMatcherBuilder};
+use grep_searcher::sinks::{Lossy, UTF8};
+use grep_searcher::{
+    BinaryDetection, Searcher, SearcherBuilder, Sink, SinkContext, SinkContextKind, SinkMatch,
+};
+use ignore::overrides::{Override, OverrideBuilder};
+use ignore::types::{Types, TypesBuilder};
+use ignore::{WalkBuilder, WalkState};
+use log::{debug, info, warn};
+use rayon::prelude::*;
+use regex::Regex;
+use serde::Serialize;
+use std::io::{BufRead, BufReader, Read};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
 use zip::ZipArchive;
 
-/// Maximum file size to scan (100MB). Files larger than this are skipped.
-const MAX_FILE_SIZE: u64 = 100 * 1024 * 1024;
-
-/// Maximum size for files inside archives (50MB)
-const MAX_ARCHIVE_ENTRY_SIZE: u64 = 50 * 1024 * 1024;
-
 /// Represents a single match found during scanning
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct Match {
     pub file_path: String,
     pub line_number: Option<usize>,
     pub matched_text: String,
     pub archive_entry: Option<String>,
+    /// Byte offset of the match within the file or archive entry (strings mode)
+    pub byte_offset: Option<u64>,
+    /// Lines before the match requested with `grep -B/-C`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub context_before: Vec<ContextLine>,
+    /// Lines after the match requested with `grep -A/-C`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub context_after: Vec<ContextLine>,
+    /// Byte ranges of the pattern within `matched_text`, for highlighting
+    #[serde(skip)]
+    pub spans: Vec<(usize, usize)>,
+}
+
+/// A non-matching line printed around a grep match
+#[derive(Debug, Clone, Serialize)]
+pub struct ContextLine {
+    pub line_number: usize,
+    pub text: String,
 }
 
 // Alias for compatibility if needed, but we use Match struct now for general search
 pub type FlagMatch = Match;
 
 /// Result of a search operation
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize)]
 pub struct SearchReport {
     pub matches: Vec<Match>,
     pub files_scanned: usize,
     pub files_skipped: usize,
+    /// Files and archive entries that were skipped, with the reason
+    pub skipped: Vec<SkippedEntry>,
     pub errors: Vec<String>,
 }
 
@@ -42,17 +71,58 @@ impl SearchReport {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Everything but the matches, for output formats that stream those
+    pub fn summary(&self) -> SearchSummary<'_> {
+        SearchSummary {
+            files_scanned: self.files_scanned,
+            files_skipped: self.files_skipped,
+            skipped: &self.skipped,
+            errors: &self.errors,
+        }
+    }
 }
 
-#[derive(Debug)]
+/// Search totals, skipped entries and errors without the matches
+#[derive(Debug, Serialize)]
+pub struct SearchSummary<'a> {
+    pub files_scanned: usize,
+    pub files_skipped: usize,
+    pub skipped: &'a [SkippedEntry],
+    pub errors: &'a [String],
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct SearchResult {
     pub path: PathBuf,
+    /// Fuzzy match score blended with frecency
     pub score: i64,
+    /// How often and how recently this directory was jumped to
+    pub frecency: f64,
+    /// Fields from the project manifest, if any
+    pub status: Option<String>,
+    pub language: Option<String>,
+    /// Comma-separated, so results stay flat for CSV output
+    pub tags: String,
+    pub description: Option<String>,
 }
 
-pub fn find_project(config: &Config, query: &str) -> Result<Vec<SearchResult>> {
-    let matcher = SkimMatcherV2::default();
-    let mut results = Vec::new();
+/// A directory `wardex find` can jump to
+#[derive(Debug, Clone)]
+pub struct ProjectCandidate {
+    pub path: PathBuf,
+    pub name: String,
+    pub frecency: f64,
+    pub manifest: Option<ProjectManifest>,
+}
+
+/// Score points added per unit of frecency. A directory visited a few times
+/// today outranks a slightly better fuzzy match that was never visited.
+const FRECENCY_WEIGHT: f64 = 15.0;
+
+/// Directories (depth 1 and 2) under Projects, Archives, Areas and Resources
+pub fn project_candidates(config: &Config, history: &JumpHistory) -> Vec<ProjectCandidate> {
+    let mut candidates = Vec::new();
 
     // Directories to search: Projects, Areas, Resources, Archives
     let dirs = vec![
@@ -83,285 +153,1070 @@ pub fn find_project(config: &Config, query: &str) -> Result<Vec<SearchResult>> {
 
             let path = entry.path();
             let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-
-            if let Some(score) = matcher.fuzzy_match(name, query) {
-                results.push(SearchResult {
-                    path: path.to_path_buf(),
-                    score,
-                });
-            }
+            candidates.push(ProjectCandidate {
+                name: name.to_string(),
+                frecency: history.frecency(path),
+                manifest: ProjectManifest::load(path).ok().flatten(),
+                path: path.to_path_buf(),
+            });
         }
     }
 
+    candidates
+}
+
+/// Fuzzy-match candidates against `query` and rank by blended score.
+/// Names match at full weight; manifest tags and descriptions at half, so
+/// `wardex find crypto` also finds projects tagged `crypto`. An empty query
+/// ranks purely by frecency.
+pub fn rank_projects(candidates: &[ProjectCandidate], query: &str) -> Vec<SearchResult> {
+    let matcher = SkimMatcherV2::default();
+    let mut results: Vec<SearchResult> = candidates
+        .iter()
+        .filter_map(|c| {
+            let fuzzy = if query.is_empty() {
+                0
+            } else {
+                let by_manifest = c.manifest.as_ref().and_then(|m| {
+                    m.tags
+                        .iter()
+                        .chain(m.description.as_ref())
+                        .filter_map(|field| matcher.fuzzy_match(field, query))
+                        .max()
+                        .map(|score| score / 2)
+                });
+                matcher
+                    .fuzzy_match(&c.name, query)
+                    .into_iter()
+                    .chain(by_manifest)
+                    .max()?
+            };
+            let manifest = c.manifest.as_ref();
+            Some(SearchResult {
+                path: c.path.clone(),
+                score: fuzzy + (c.frecency * FRECENCY_WEIGHT).round() as i64,
+                frecency: c.frecency,
+                status: manifest.and_then(|m| m.status).map(|s| s.to_string()),
+                language: manifest.and_then(|m| m.language.clone()),
+                tags: manifest.map(|m| m.tags.join(",")).unwrap_or_default(),
+                description: manifest.and_then(|m| m.description.clone()),
+            })
+        })
+        .collect();
+
     // Sort by score descending
-    results.sort_by(|a, b| b.score.cmp(&a.score));
-    Ok(results)
+    results.sort_by_key(|r| std::cmp::Reverse(r.score));
+    results
+}
+
+pub fn find_project(
+    config: &Config,
+    query: &str,
+    filter: &ProjectFilter,
+) -> Result<Vec<SearchResult>> {
+    let history = JumpHistory::load();
+    let candidates: Vec<ProjectCandidate> = project_candidates(config, &history)
+        .into_iter()
+        .filter(|c| filter.matches(c.manifest.as_ref()))
+        .collect();
+    Ok(rank_projects(&candidates, query))
 }
 
-pub fn content_search(config: &Config, pattern: &str) -> Result<Vec<Match>> {
-    // Projects and Resources
-    let roots = vec![
+/// Directories covered by `wardex grep` and its content index
+pub fn content_roots(config: &Config) -> Vec<PathBuf> {
+    vec![
         config.resolve_path("projects"),
         config.resolve_path("resources"),
-    ];
+    ]
+}
 
-    let mut all_matches = Vec::new();
-    let matcher = RegexMatcher::new(pattern)?;
+/// Root keys accepted by `wardex grep --in`, besides `paths.custom` keys
+pub const GREP_ROOT_KEYS: &[&str] = &[
+    "workspace",
+    "inbox",
+    "projects",
+    "areas",
+    "resources",
+    "archives",
+    "ctf",
+];
 
-    for root in roots {
-        if !root.exists() {
-            continue;
+/// Options for `wardex grep`
+#[derive(Debug, Clone, Default)]
+pub struct GrepOptions {
+    /// Root keys to search (see [`GREP_ROOT_KEYS`]); empty means projects + resources
+    pub roots: Vec<String>,
+    /// File types as known to ripgrep (`rust`, `py`, `md`, ...)
+    pub types: Vec<String>,
+    /// Include globs, or exclude globs prefixed with `!`
+    pub globs: Vec<String>,
+    pub ignore_case: bool,
+    /// Only match whole words
+    pub word: bool,
+    /// Treat the pattern as a literal string
+    pub fixed_strings: bool,
+    pub before_context: usize,
+    pub after_context: usize,
+    /// Stop searching a file after this many matching lines
+    pub max_count: Option<u64>,
+    /// Use the content index (see `wardex index`) when one exists
+    pub use_index: bool,
+}
+
+impl GrepOptions {
+    /// Regex equivalent to the matcher options, used to plan index lookups
+    fn effective_pattern(&self, pattern: &str) -> String {
+        let pattern = if self.fixed_strings {
+            regex::escape(pattern)
+        } else {
+            pattern.to_string()
+        };
+        if self.ignore_case {
+            format!("(?i){}", pattern)
+        } else {
+            pattern
+        }
+    }
+}
+
+/// Resolve `wardex grep --in` keys to directories
+pub fn grep_roots(config: &Config, keys: &[String]) -> Result<Vec<PathBuf>> {
+    if keys.is_empty() {
+        return Ok(content_roots(config));
+    }
+
+    keys.iter()
+        .map(|key| match key.as_str() {
+            "ctf" => ctf::get_active_event_root(),
+            k if GREP_ROOT_KEYS.contains(&k) || config.paths.custom.contains_key(k) => {
+                Ok(config.resolve_path(k))
+            }
+            k => {
+                let mut valid: Vec<&str> = GREP_ROOT_KEYS.to_vec();
+                valid.extend(config.paths.custom.keys().map(|k| k.as_str()));
+                anyhow::bail!("Unknown root '{}'. Valid roots: {}", k, valid.join(", "))
+            }
+        })
+        .collect()
+}
+
+/// Type and glob filters, applied to walkers and to index candidates alike
+struct FileFilter {
+    types: Types,
+    overrides: Vec<(PathBuf, Override)>,
+}
+
+impl FileFilter {
+    fn new(roots: &[PathBuf], options: &GrepOptions) -> Result<Self> {
+        let mut types = TypesBuilder::new();
+        types.add_defaults();
+        for name in &options.types {
+            types.select(name);
+        }
+        let types = types.build().context("Invalid file type")?;
+
+        let overrides = roots
+            .iter()
+            .map(|root| {
+                let mut builder = OverrideBuilder::new(root);
+                for glob in &options.globs {
+                    builder.add(glob)?;
+                }
+                Ok((root.clone(), builder.build()?))
+            })
+            .collect::<Result<_>>()
+            .context("Invalid glob")?;
+
+        Ok(Self { types, overrides })
+    }
+
+    fn walker(&self, root: &Path) -> WalkBuilder {
+        let mut builder = WalkBuilder::new(root);
+        builder.types(self.types.clone());
+        if let Some((_, overrides)) = self.overrides.iter().find(|(r, _)| r == root) {
+            builder.overrides(overrides.clone());
+        }
+        builder
+    }
+
+    /// Whether a file under one of the roots passes the filters
+    fn allows(&self, path: &Path) -> bool {
+        let Some((_, overrides)) = self.overrides.iter().find(|(r, _)| path.starts_with(r)) else {
+            return false;
+        };
+        !overrides.matched(path, false).is_ignore() && !self.types.matched(path, false).is_ignore()
+    }
+}
+
+/// Grep the chosen roots for `pattern`. With `use_index`, the persistent
+/// content index (see `wardex index`) narrows down which files are read when
+/// it covers the roots; otherwise every file is scanned.
+pub fn content_search(config: &Config, pattern: &str, options: &GrepOptions) -> Result<Vec<Match>> {
+    let matcher = RegexMatcherBuilder::new()
+        .case_insensitive(options.ignore_case)
+        .word(options.word)
+        .fixed_strings(options.fixed_strings)
+        .build(pattern)?;
+    let max_size = config.search.max_file_size();
+    let roots: Vec<PathBuf> = grep_roots(config, &options.roots)?
+        .into_iter()
+        .filter(|r| r.exists())
+        .collect();
+    let filter = FileFilter::new(&roots, options)?;
+    let grep = |path: &Path| grep_file(&matcher, path, max_size, options);
+
+    let indexed_roots = content_roots(config);
+    let covered = roots
+        .iter()
+        .all(|r| indexed_roots.iter().any(|i| r.starts_with(i)));
+
+    if options.use_index && covered {
+        match index::ContentIndex::open(config) {
+            Ok(Some(index)) => {
+                let plan = index.plan(&options.effective_pattern(pattern))?;
+                let files: Vec<&PathBuf> = plan.files.iter().filter(|p| filter.allows(p)).collect();
+                info!(
+                    "Index: grepping {} of {} files ({} changed since indexing)",
+                    files.len(),
+                    plan.indexed_files,
+                    plan.stale
+                );
+                let matches = files.par_iter().flat_map_iter(|path| grep(path)).collect();
+                return Ok(matches);
+            }
+            Ok(None) => debug!("No content index, scanning all files (run `wardex index`)"),
+            Err(e) => warn!("Ignoring content index: {:#}", e),
         }
+    }
 
-        let walker = WalkBuilder::new(root).build();
+    let mut all_matches = Vec::new();
+    for root in &roots {
+        let walker = filter.walker(root).build();
 
         for result in walker.filter_map(|e| e.ok()) {
             let path = result.path();
             if path.is_file() {
-                // Determine if we should search this file (skip binaries, big files etc)
-                if let Ok(metadata) = path.metadata() {
-                    if metadata.len() > MAX_FILE_SIZE {
-                        continue;
-                    }
-                }
+                all_matches.extend(grep(path));
+            }
+        }
+    }
 
-                let mut matches_in_file = Vec::new();
-                let file_path = path.display().to_string();
+    Ok(all_matches)
+}
+
+/// Grep a single text file line by line, skipping binaries and big files
+fn grep_file(
+    matcher: &RegexMatcher,
+    path: &Path,
+    max_size: Option<u64>,
+    options: &GrepOptions,
+) -> Vec<Match> {
+    if let (Ok(metadata), Some(max)) = (path.metadata(), max_size) {
+        if metadata.len() > max {
+            return Vec::new();
+        }
+    }
+
+    let mut sink = GrepSink {
+        matcher,
+        file_path: path.display().to_string(),
+        max_count: options.max_count,
+        matches: Vec::new(),
+        pending_before: Vec::new(),
+    };
 
-                let _ = SearcherBuilder::new()
-                    .binary_detection(BinaryDetection::quit(b'\x00'))
-                    .build()
-                    .search_path(
-                        &matcher,
-                        path,
-                        UTF8(|line_num, line| {
-                            matches_in_file.push(Match {
-                                file_path: file_path.clone(),
-                                line_number: Some(line_num as usize),
-                                matched_text: line.trim().to_string(),
-                                archive_entry: None,
-                            });
-                            Ok(true)
-                        }),
-                    );
-
-                all_matches.extend(matches_in_file);
+    let _ = SearcherBuilder::new()
+        .binary_detection(BinaryDetection::quit(b'\x00'))
+        .before_context(options.before_context)
+        .after_context(options.after_context)
+        .build()
+        .search_path(matcher, path, &mut sink);
+
+    sink.matches
+}
+
+/// Collects grep matches together with their context lines
+struct GrepSink<'a> {
+    matcher: &'a RegexMatcher,
+    file_path: String,
+    max_count: Option<u64>,
+    matches: Vec<Match>,
+    /// Before-context seen since the last match
+    pending_before: Vec<ContextLine>,
+}
+
+fn sink_line(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes)
+        .trim_end_matches(['\r', '\n'])
+        .to_string()
+}
+
+impl Sink for GrepSink<'_> {
+    type Error = std::io::Error;
+
+    fn matched(&mut self, _: &Searcher, mat: &SinkMatch<'_>) -> Result<bool, Self::Error> {
+        let text = sink_line(mat.bytes());
+        let mut spans = Vec::new();
+        let _ = self.matcher.find_iter(text.as_bytes(), |m| {
+            if !m.is_empty() {
+                spans.push((m.start(), m.end()));
+            }
+            true
+        });
+
+        self.matches.push(Match {
+            file_path: self.file_path.clone(),
+            line_number: mat.line_number().map(|n| n as usize),
+            matched_text: text,
+            context_before: std::mem::take(&mut self.pending_before),
+            spans,
+            ..Default::default()
+        });
+        Ok(self
+            .max_count
+            .is_none_or(|max| (self.matches.len() as u64) < max))
+    }
+
+    fn context(&mut self, _: &Searcher, ctx: &SinkContext<'_>) -> Result<bool, Self::Error> {
+        let line = ContextLine {
+            line_number: ctx.line_number().unwrap_or(0) as usize,
+            text: sink_line(ctx.bytes()),
+        };
+        match ctx.kind() {
+            SinkContextKind::Before => self.pending_before.push(line),
+            SinkContextKind::After => {
+                if let Some(last) = self.matches.last_mut() {
+                    last.context_after.push(line);
+                }
             }
+            SinkContextKind::Other => {}
         }
+        Ok(true)
     }
+}
 
-    Ok(all_matches)
+/// Live counters updated while a flag search is running.
+///
+/// Shared between the walker threads and whoever renders progress, so all
+/// fields are atomics.
+#[derive(Debug, Default)]
+pub struct SearchProgress {
+    pub files_scanned: AtomicUsize,
+    pub bytes_scanned: AtomicU64,
+}
+
+/// A file or archive entry that was not (fully) scanned
+#[derive(Debug, Clone, Serialize)]
+pub struct SkippedEntry {
+    pub file_path: String,
+    pub archive_entry: Option<String>,
+    pub reason: String,
+}
+
+/// Options controlling a flag search
+#[derive(Debug, Clone)]
+pub struct FlagSearchOptions {
+    /// Custom regex; defaults to `ctf{...}` / `flag{...}` (case-insensitive)
+    pub pattern: Option<String>,
+    /// Extract printable ASCII/UTF-16 strings from binary files and archive
+    /// entries and match against those, like `strings(1)`
+    pub strings: bool,
+    /// Minimum length of an extracted string in strings mode
+    pub min_string_len: usize,
+}
+
+impl Default for FlagSearchOptions {
+    fn default() -> Self {
+        Self {
+            pattern: None,
+            strings: false,
+            min_string_len: 4,
+        }
+    }
+}
+
+/// Matches and skipped entries from scanning a single file
+#[derive(Debug, Default)]
+struct FileScan {
+    matches: Vec<Match>,
+    skipped: Vec<SkippedEntry>,
 }
 
 /// Search for flags in files under the given path
 pub fn find_flags(path: &Path, pattern: Option<String>) -> Result<SearchReport> {
-    let pattern_str = pattern.as_deref().unwrap_or(r"(?i)(ctf|flag)\{.*?\}");
-    let matcher = RegexMatcher::new(pattern_str).context("Invalid regex pattern")?;
-
-    let mut report = SearchReport::new();
-
-    for entry in WalkBuilder::new(path).build().filter_map(|e| e.ok()) {
-        let entry_path = entry.path();
-        if entry_path.is_file() {
-            // Check file size
-            if let Ok(metadata) = fs_err::metadata(entry_path) {
-                if metadata.len() > MAX_FILE_SIZE {
-                    report.files_skipped += 1;
-                    continue;
+    let options = FlagSearchOptions {
+        pattern,
+        ..Default::default()
+    };
+    find_flags_streaming(
+        path,
+        &options,
+        &SearchConfig::default(),
+        &SearchProgress::default(),
+        |_| {},
+    )
+}
+
+/// Search for flags in parallel, calling `on_match` for every match as soon as
+/// it is found. Counters in `progress` are updated as files are scanned.
+pub fn find_flags_streaming<F>(
+    path: &Path,
+    options: &FlagSearchOptions,
+    limits: &SearchConfig,
+    progress: &SearchProgress,
+    on_match: F,
+) -> Result<SearchReport>
+where
+    F: Fn(&Match) + Sync,
+{
+    let pattern_str = options
+        .pattern
+        .as_deref()
+        .unwrap_or(r"(?i)(ctf|flag)\{.*?\}");
+    let scanner = Scanner {
+        matcher: RegexMatcher::new(pattern_str).context("Invalid regex pattern")?,
+        extractor: Regex::new(pattern_str).context("Invalid regex pattern")?,
+        bytes_extractor: regex::bytes::Regex::new(pattern_str).context("Invalid regex pattern")?,
+        limits,
+        strings_min_len: options.strings.then_some(options.min_string_len.max(1)),
+    };
+
+    let report = Mutex::new(SearchReport::new());
+
+    WalkBuilder::new(path).build_parallel().run(|| {
+        let scanner = &scanner;
+        let report = &report;
+        let on_match = &on_match;
+        Box::new(move |entry| {
+            let entry = match entry {
+                Ok(e) => e,
+                Err(e) => {
+                    report.lock().unwrap().errors.push(e.to_string());
+                    return WalkState::Continue;
                 }
+            };
+
+            if !entry.file_type().map(|ft| ft.is_file()).unwrap_or(false) {
+                return WalkState::Continue;
             }
 
-            if let Some(ext) = entry_path.extension().and_then(|s| s.to_str()) {
-                let result = match ext {
-                    "zip" => scan_zip(entry_path, pattern_str),
-                    "tar" => scan_tar(entry_path, pattern_str),
-                    "gz" | "tgz" => scan_tar_gz(entry_path, pattern_str),
-                    _ => scan_file(entry_path, &matcher),
-                };
-                match result {
-                    Ok(matches) => {
-                        report.files_scanned += 1;
-                        report.matches.extend(matches);
-                    }
-                    Err(e) => {
-                        report
-                            .errors
-                            .push(format!("{}: {}", entry_path.display(), e));
-                    }
+            let entry_path = entry.path();
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            if let Some(max) = limits.max_file_size().filter(|max| size > *max) {
+                let mut r = report.lock().unwrap();
+                r.files_skipped += 1;
+                r.skipped.push(SkippedEntry {
+                    file_path: entry_path.display().to_string(),
+                    archive_entry: None,
+                    reason: too_large(size, max),
+                });
+                return WalkState::Continue;
+            }
+
+            let result = scanner.scan_entry(entry_path);
+            progress.files_scanned.fetch_add(1, Ordering::Relaxed);
+            progress.bytes_scanned.fetch_add(size, Ordering::Relaxed);
+
+            match result {
+                Ok(scan) => {
+                    scan.matches.iter().for_each(on_match);
+                    let mut r = report.lock().unwrap();
+                    r.files_scanned += 1;
+                    r.matches.extend(scan.matches);
+                    r.skipped.extend(scan.skipped);
                 }
-            } else {
-                match scan_file(entry_path, &matcher) {
-                    Ok(matches) => {
-                        report.files_scanned += 1;
-                        report.matches.extend(matches);
-                    }
-                    Err(e) => {
-                        report
-                            .errors
-                            .push(format!("{}: {}", entry_path.display(), e));
-                    }
+                Err(e) => {
+                    report
+                        .lock()
+                        .unwrap()
+                        .errors
+                        .push(format!("{}: {}", entry_path.display(), e));
                 }
             }
+
+            WalkState::Continue
+        })
+    });
+
+    Ok(report.into_inner().unwrap())
+}
+
+fn too_large(size: u64, max: u64) -> String {
+    format!(
+        "size {:.1} MB exceeds limit of {:.1} MB",
+        size as f64 / 1024.0 / 1024.0,
+        max as f64 / 1024.0 / 1024.0
+    )
+}
+
+/// Number of leading bytes inspected to decide whether content is binary
+const BINARY_SNIFF_LEN: usize = 8 * 1024;
+
+/// Peek at the start of a buffered reader and report whether it contains a NUL
+fn looks_binary<R: BufRead>(reader: &mut R) -> bool {
+    reader
+        .fill_buf()
+        .map(|buf| buf.contains(&0))
+        .unwrap_or(false)
+}
+
+/// Error returned by `LineLimitReader` once a line exceeds its limit
+#[derive(Debug)]
+struct LineTooLong;
+
+impl std::fmt::Display for LineTooLong {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line exceeds buffer limit")
+    }
+}
+
+impl std::error::Error for LineTooLong {}
+
+/// Reader that fails with `LineTooLong` as soon as a line grows past `limit`
+/// bytes. Lines end at `\n` or NUL, matching how the searcher splits them
+/// with `BinaryDetection::convert`, so it trips before the searcher's own
+/// heap limit does.
+struct LineLimitReader<R> {
+    inner: R,
+    limit: usize,
+    line_len: usize,
+}
+
+impl<R: Read> LineLimitReader<R> {
+    fn new(inner: R, limit: usize) -> Self {
+        Self {
+            inner,
+            limit,
+            line_len: 0,
+        }
+    }
+}
+
+impl<R: Read> Read for LineLimitReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        for (i, segment) in buf[..n].split(|&b| b == b'\n' || b == 0).enumerate() {
+            self.line_len = if i == 0 {
+                self.line_len + segment.len()
+            } else {
+                segment.len()
+            };
+            if self.line_len > self.limit {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    LineTooLong,
+                ));
+            }
         }
+        Ok(n)
     }
-    Ok(report)
 }
 
-/// Scan a single file using grep-searcher (ripgrep's library)
-fn scan_file(path: &Path, matcher: &RegexMatcher) -> Result<Vec<Match>> {
-    let mut matches = Vec::new();
-    let file_path = path.display().to_string();
+/// Per-search state shared by all walker threads
+struct Scanner<'a> {
+    /// Line matcher used by grep-searcher
+    matcher: RegexMatcher,
+    /// Same pattern, used to pull exact match spans out of lines and strings
+    extractor: Regex,
+    /// Same pattern over raw bytes, for reassembled network streams
+    bytes_extractor: regex::bytes::Regex,
+    limits: &'a SearchConfig,
+    /// `Some(min_len)` when strings mode is enabled
+    strings_min_len: Option<usize>,
+}
 
-    let mut searcher = SearcherBuilder::new()
-        .binary_detection(BinaryDetection::quit(b'\x00'))
-        .build();
+impl Scanner<'_> {
+    /// Dispatch a single file to the archive or plain-file scanner based on its extension
+    fn scan_entry(&self, path: &Path) -> Result<FileScan> {
+        match path.extension().and_then(|s| s.to_str()) {
+            Some("zip") => self.scan_zip(path),
+            Some("tar") => {
+                let archive = tar::Archive::new(File::open(path)?);
+                self.scan_tar_entries(path, archive)
+            }
+            Some("gz") | Some("tgz") => {
+                let archive = tar::Archive::new(GzDecoder::new(File::open(path)?));
+                self.scan_tar_entries(path, archive)
+            }
+            Some("pcap") | Some("pcapng") | Some("cap") => self.scan_capture(path),
+            _ => self.scan_plain(path),
+        }
+    }
 
-    // Use UTF8 sink for line-by-line matching
-    let result = searcher.search_path(
-        matcher,
-        path,
-        UTF8(|line_num, line| {
-            // The line already matched - extract the match text
-            // Use regex to find exact match positions in the line
-            // Note: matcher in grep-searcher is just for line matching, we re-verify with regex to capture text if needed
-            // Actually, we can just return the line or re-match.
-            // The original implementation re-matched to handle multiple flags per line.
-
-            // We need to reconstruct the regex from matcher or passed pattern?
-            // Since we passed matcher, we don't have pattern string here easily unless we passed it.
-            // But find_flags is specific to flags. content_search is generic grep.
-            // Let's assume this is mostly for find_flags since content_search calls search_path directly loop.
-
-            // Wait, find_flags needs to find specific "flag{...}" pattern even if line matched.
-            // But here we don't have the regex object.
-            // We can just grab the whole line or improve logic.
-            // For now, let's keep the logic close to original but simpler:
-            // just push the line matching.
+    /// Scan a regular file, switching to string extraction for binaries in strings mode
+    fn scan_plain(&self, path: &Path) -> Result<FileScan> {
+        if let Some(min_len) = self.strings_min_len {
+            let mut reader = BufReader::with_capacity(BINARY_SNIFF_LEN, File::open(path)?);
+            if looks_binary(&mut reader) {
+                let file_path = path.display().to_string();
+                let matches = self
+                    .scan_strings(reader, &file_path, None, min_len)
+                    .with_context(|| format!("Failed to read {}", file_path))?;
+                return Ok(FileScan {
+                    matches,
+                    skipped: Vec::new(),
+                });
+            }
+        }
 
+        Ok(FileScan {
+            matches: self.scan_file(path)?,
+            skipped: Vec::new(),
+        })
+    }
+
+    /// Scan a single file using grep-searcher (ripgrep's library)
+    fn scan_file(&self, path: &Path) -> Result<Vec<Match>> {
+        let mut matches = Vec::new();
+        let file_path = path.display().to_string();
+
+        // In strings mode the file was already sniffed as text, so a NUL
+        // further in must not end the scan: treat it as a line break instead
+        let strings_mode = self.strings_min_len.is_some();
+        let mut searcher = SearcherBuilder::new()
+            .binary_detection(if strings_mode {
+                BinaryDetection::convert(b'\x00')
+            } else {
+                BinaryDetection::quit(b'\x00')
+            })
+            .build();
+
+        let mut on_line = |line_num: u64, line: &str| {
             matches.push(Match {
                 file_path: file_path.clone(),
                 archive_entry: None,
-                matched_text: line.trim().to_string(), // Simplified from original regex extraction
+                matched_text: line.trim().to_string(),
                 line_number: Some(line_num as usize),
+                byte_offset: None,
+                ..Default::default()
             });
             Ok(true)
-        }),
-    );
-
-    match result {
-        Ok(_) => Ok(matches),
-        Err(e) => {
-            // Binary file or read error - try fallback if needed
-            log::debug!("grep-searcher failed for {}: {}", path.display(), e);
-            Ok(matches)
+        };
+        // Use UTF8 sink for line-by-line matching; binary tails need the lossy one
+        let result = if strings_mode {
+            searcher.search_path(&self.matcher, path, Lossy(&mut on_line))
+        } else {
+            searcher.search_path(&self.matcher, path, UTF8(&mut on_line))
+        };
+
+        match result {
+            Ok(_) => Ok(matches),
+            Err(e) => {
+                // Binary file or read error - try fallback if needed
+                log::debug!("grep-searcher failed for {}: {}", path.display(), e);
+                Ok(matches)
+            }
         }
     }
-}
 
-/// Scan a buffer (used for archive entries)
-fn scan_buffer(
-    buffer: &[u8],
-    file_path: &str,
-    archive_entry: Option<String>,
-    pattern: &str,
-) -> Vec<Match> {
-    let mut matches = Vec::new();
-    let text = String::from_utf8_lossy(buffer);
-
-    if let Ok(regex) = regex::RegexBuilder::new(pattern)
-        .case_insensitive(true)
-        .build()
-    {
-        for mat in regex.find_iter(&text) {
-            matches.push(Match {
-                file_path: file_path.to_string(),
-                archive_entry: archive_entry.clone(),
-                matched_text: mat.as_str().to_string(),
-                line_number: None,
-            });
+    /// Scan one archive entry, returning its matches and, if the stream
+    /// could not be finished, the reason.
+    fn scan_archive_entry<R: Read>(
+        &self,
+        reader: R,
+        file_path: &str,
+        archive_entry: &str,
+    ) -> (Vec<Match>, Option<String>) {
+        let mut reader = BufReader::with_capacity(BINARY_SNIFF_LEN, reader);
+        match self.strings_min_len {
+            Some(min_len) if looks_binary(&mut reader) => {
+                match self.scan_strings(reader, file_path, Some(archive_entry), min_len) {
+                    Ok(matches) => (matches, None),
+                    Err(e) => (Vec::new(), Some(format!("read error: {}", e))),
+                }
+            }
+            _ => self.scan_stream(reader, file_path, archive_entry),
         }
     }
 
-    matches
-}
+    /// Stream one archive entry through grep-searcher.
+    ///
+    /// Lines are capped at `buffer_limit_mb`, which bounds the searcher's line buffer.
+    /// NUL bytes are treated as line breaks so binary entries are still searched
+    /// without producing huge "lines".
+    fn scan_stream<R: Read>(
+        &self,
+        reader: R,
+        file_path: &str,
+        archive_entry: &str,
+    ) -> (Vec<Match>, Option<String>) {
+        let mut matches = Vec::new();
+        let limit = self.limits.buffer_limit();
 
-fn scan_zip(path: &Path, pattern: &str) -> Result<Vec<Match>> {
-    let mut all_matches = Vec::new();
-    let file = File::open(path)?;
-    let mut archive = ZipArchive::new(file).context("Failed to open zip")?;
+        let mut searcher = SearcherBuilder::new()
+            .binary_detection(BinaryDetection::convert(b'\x00'))
+            // Headroom so `LineLimitReader` reports long lines before the
+            // searcher's own allocation limit does
+            .heap_limit(limit.map(|l| l.saturating_mul(2)))
+            .build();
 
-    for i in 0..archive.len() {
-        let mut file = archive.by_index(i)?;
-        let name = file.name().to_string();
+        let result = searcher.search_reader(
+            &self.matcher,
+            LineLimitReader::new(reader, limit.unwrap_or(usize::MAX)),
+            Lossy(|line_num, line| {
+                for mat in self.extractor.find_iter(line) {
+                    matches.push(Match {
+                        file_path: file_path.to_string(),
+                        archive_entry: Some(archive_entry.to_string()),
+                        matched_text: mat.as_str().to_string(),
+                        line_number: Some(line_num as usize),
+                        byte_offset: None,
+                        ..Default::default()
+                    });
+                }
+                Ok(true)
+            }),
+        );
 
-        if file.is_dir() {
-            continue;
+        let error = result.err().map(|e| {
+            if e.get_ref().is_some_and(|inner| inner.is::<LineTooLong>()) {
+                format!(
+                    "line longer than buffer limit of {} MB",
+                    self.limits.buffer_limit_mb
+                )
+            } else {
+                format!("read error: {}", e)
+            }
+        });
+        (matches, error)
+    }
+
+    /// Extract printable strings from a byte stream and match against them,
+    /// recording the byte offset of each match.
+    fn scan_strings<R: Read>(
+        &self,
+        mut reader: R,
+        file_path: &str,
+        archive_entry: Option<&str>,
+        min_len: usize,
+    ) -> std::io::Result<Vec<Match>> {
+        let mut matches = Vec::new();
+        let max_run = self.limits.buffer_limit().unwrap_or(usize::MAX);
+        let mut on_string = |offset: u64, char_width: u64, text: &str| {
+            for mat in self.extractor.find_iter(text) {
+                matches.push(Match {
+                    file_path: file_path.to_string(),
+                    archive_entry: archive_entry.map(String::from),
+                    matched_text: mat.as_str().to_string(),
+                    line_number: None,
+                    byte_offset: Some(offset + mat.start() as u64 * char_width),
+                    ..Default::default()
+                });
+            }
+        };
+        let mut strings = StringsExtractor::new(min_len, max_run);
+
+        let mut buf = vec![0u8; 64 * 1024];
+        loop {
+            let n = match reader.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            strings.feed(&buf[..n], &mut on_string);
         }
+        strings.finish(&mut on_string);
 
-        if file.size() > MAX_ARCHIVE_ENTRY_SIZE {
-            continue;
+        Ok(matches)
+    }
+
+    /// Reassemble TCP/UDP streams and HTTP bodies from a capture and search
+    /// each of them. Offsets are relative to the start of the stream.
+    fn scan_capture(&self, path: &Path) -> Result<FileScan> {
+        let mut reader = BufReader::new(File::open(path)?);
+        if !pcap::is_capture(reader.fill_buf()?) {
+            return self.scan_plain(path);
         }
 
-        let mut buffer = Vec::new();
-        if file.read_to_end(&mut buffer).is_ok() {
-            let matches = scan_buffer(&buffer, &path.display().to_string(), Some(name), pattern);
-            all_matches.extend(matches);
+        let file_path = path.display().to_string();
+        let streams = pcap::extract_streams(
+            reader,
+            self.limits.max_file_size(),
+            self.limits.max_archive_entry_size(),
+        )?;
+
+        let mut scan = FileScan::default();
+        for stream in streams {
+            for mat in self.bytes_extractor.find_iter(&stream.data) {
+                scan.matches.push(Match {
+                    file_path: file_path.clone(),
+                    archive_entry: Some(stream.label.clone()),
+                    matched_text: String::from_utf8_lossy(mat.as_bytes()).to_string(),
+                    line_number: None,
+                    byte_offset: Some(mat.start() as u64),
+                    ..Default::default()
+                });
+            }
         }
+        Ok(scan)
+    }
+
+    fn scan_zip(&self, path: &Path) -> Result<FileScan> {
+        let mut scan = FileScan::default();
+        let file_path = path.display().to_string();
+        let file = File::open(path)?;
+        let mut archive = ZipArchive::new(file).context("Failed to open zip")?;
+
+        for i in 0..archive.len() {
+            let entry_name = archive.name_for_index(i).map(String::from);
+            let entry = match archive.by_index(i) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    scan.skipped.push(SkippedEntry {
+                        file_path: file_path.clone(),
+                        archive_entry: entry_name,
+                        reason: format!("unreadable entry: {}", e),
+                    });
+                    continue;
+                }
+            };
+            let name = entry.name().to_string();
+
+            if entry.is_dir() {
+                continue;
+            }
+
+            if let Some(max) = self
+                .limits
+                .max_archive_entry_size()
+                .filter(|max| entry.size() > *max)
+            {
+                scan.skipped.push(SkippedEntry {
+                    file_path: file_path.clone(),
+                    archive_entry: Some(name),
+                    reason: too_large(entry.size(), max),
+                });
+                continue;
+            }
+
+            let (matches, error) = self.scan_archive_entry(entry, &file_path, &name);
+            scan.matches.extend(matches);
+            if let Some(reason) = error {
+                scan.skipped.push(SkippedEntry {
+                    file_path: file_path.clone(),
+                    archive_entry: Some(name),
+                    reason,
+                });
+            }
+        }
+        Ok(scan)
+    }
+
+    fn scan_tar_entries<R: Read>(
+        &self,
+        path: &Path,
+        mut archive: tar::Archive<R>,
+    ) -> Result<FileScan> {
+        let mut scan = FileScan::default();
+        let file_path = path.display().to_string();
+
+        for entry in archive.entries()? {
+            let entry = entry?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let name = entry.path()?.to_string_lossy().to_string();
+
+            if let Some(max) = self
+                .limits
+                .max_archive_entry_size()
+                .filter(|max| entry.size() > *max)
+            {
+                scan.skipped.push(SkippedEntry {
+                    file_path: file_path.clone(),
+                    archive_entry: Some(name),
+                    reason: too_large(entry.size(), max),
+                });
+                continue;
+            }
+
+            let (matches, error) = self.scan_archive_entry(entry, &file_path, &name);
+            scan.matches.extend(matches);
+            if let Some(reason) = error {
+                scan.skipped.push(SkippedEntry {
+                    file_path: file_path.clone(),
+                    archive_entry: Some(name),
+                    reason,
+                });
+            }
+        }
+        Ok(scan)
     }
-    Ok(all_matches)
 }
 
-fn scan_tar(path: &Path, pattern: &str) -> Result<Vec<Match>> {
-    let mut all_matches = Vec::new();
-    let file = File::open(path)?;
-    let mut archive = tar::Archive::new(file);
+fn is_printable(b: u8) -> bool {
+    (0x20..=0x7e).contains(&b) || b == b'\t'
+}
 
-    for entry in archive.entries()? {
-        let mut entry = entry?;
-        let entry_path = entry.path()?.to_string_lossy().to_string();
+/// A run of printable characters being collected
+#[derive(Default)]
+struct StringRun {
+    text: String,
+    /// Byte offset of the first character in the stream
+    start: u64,
+}
 
-        if entry.size() > MAX_ARCHIVE_ENTRY_SIZE {
-            continue;
+/// Incremental `strings(1)`-style extractor.
+///
+/// Tracks printable ASCII runs plus UTF-16LE runs (printable byte followed by
+/// NUL) at both even and odd alignment, so it can be fed a stream in chunks.
+/// Runs longer than `max_run` are emitted early to keep memory bounded.
+struct StringsExtractor {
+    min_len: usize,
+    max_run: usize,
+    offset: u64,
+    prev: Option<u8>,
+    ascii: StringRun,
+    /// UTF-16LE runs indexed by the parity of their starting offset
+    utf16: [StringRun; 2],
+}
+
+impl StringsExtractor {
+    fn new(min_len: usize, max_run: usize) -> Self {
+        Self {
+            min_len,
+            max_run,
+            offset: 0,
+            prev: None,
+            ascii: StringRun::default(),
+            utf16: Default::default(),
         }
+    }
+
+    /// Feed a chunk; `emit(offset, char_width, text)` is called per finished string
+    fn feed<F: FnMut(u64, u64, &str)>(&mut self, chunk: &[u8], emit: &mut F) {
+        for &b in chunk {
+            let pos = self.offset;
+
+            // ASCII runs
+            if is_printable(b) {
+                if self.ascii.text.is_empty() {
+                    self.ascii.start = pos;
+                }
+                self.ascii.text.push(b as char);
+                if self.ascii.text.len() >= self.max_run {
+                    Self::flush(&mut self.ascii, self.min_len, 1, emit);
+                }
+            } else {
+                Self::flush(&mut self.ascii, self.min_len, 1, emit);
+            }
 
-        let mut buffer = Vec::new();
-        if entry.read_to_end(&mut buffer).is_ok() {
-            let matches = scan_buffer(
-                &buffer,
-                &path.display().to_string(),
-                Some(entry_path),
-                pattern,
-            );
-            all_matches.extend(matches);
+            // UTF-16LE: the previous byte and this one form a code unit
+            // starting at `pos - 1`
+            if let Some(low) = self.prev {
+                let run = &mut self.utf16[((pos - 1) % 2) as usize];
+                if b == 0 && is_printable(low) {
+                    if run.text.is_empty() {
+                        run.start = pos - 1;
+                    }
+                    run.text.push(low as char);
+                    if run.text.len() >= self.max_run {
+                        Self::flush(run, self.min_len, 2, emit);
+                    }
+                } else {
+                    Self::flush(run, self.min_len, 2, emit);
+                }
+            }
+
+            self.prev = Some(b);
+            self.offset += 1;
         }
     }
-    Ok(all_matches)
+
+    /// Emit any runs still pending at end of stream
+    fn finish<F: FnMut(u64, u64, &str)>(&mut self, emit: &mut F) {
+        Self::flush(&mut self.ascii, self.min_len, 1, emit);
+        for run in self.utf16.iter_mut() {
+            Self::flush(run, self.min_len, 2, emit);
+        }
+    }
+
+    fn flush<F: FnMut(u64, u64, &str)>(
+        run: &mut StringRun,
+        min_len: usize,
+        char_width: u64,
+        emit: &mut F,
+    ) {
+        if run.text.len() >= min_len {
+            emit(run.start, char_width, &run.text);
+        }
+        run.text.clear();
+    }
 }
 
-fn scan_tar_gz(path: &Path, pattern: &str) -> Result<Vec<Match>> {
-    let mut all_matches = Vec::new();
-    let file = File::open(path)?;
-    let tar = flate2::read::GzDecoder::new(file);
-    let mut archive = tar::Archive::new(tar);
+/// Print grep results like `path:line: text`, with `path-line- text` for
+/// context lines and `--` between non-adjacent groups.
+pub fn print_grep_matches(matches: &[Match], color: bool, with_context: bool) {
+    const PATH: &str = "\x1b[35m";
+    const LINE: &str = "\x1b[32m";
+    const HIT: &str = "\x1b[1;31m";
+    const RESET: &str = "\x1b[0m";
 
-    for entry in archive.entries()? {
-        let mut entry = entry?;
-        let entry_path = entry.path()?.to_string_lossy().to_string();
+    let paint = |code: &str, text: &str| {
+        if color {
+            format!("{}{}{}", code, text, RESET)
+        } else {
+            text.to_string()
+        }
+    };
+    let context_line = |path: &str, line: &ContextLine| {
+        println!(
+            "{}-{}- {}",
+            paint(PATH, path),
+            paint(LINE, &line.line_number.to_string()),
+            line.text
+        );
+    };
 
-        if entry.size() > MAX_ARCHIVE_ENTRY_SIZE {
-            continue;
+    let mut last: Option<(&str, usize)> = None;
+    for m in matches {
+        let line_number = m.line_number.unwrap_or(0);
+        let first = m
+            .context_before
+            .first()
+            .map(|c| c.line_number)
+            .unwrap_or(line_number);
+        if with_context {
+            if let Some((path, end)) = last {
+                if path != m.file_path || first > end + 1 {
+                    println!("--");
+                }
+            }
+        }
+
+        let mut text = String::new();
+        let mut pos = 0;
+        for &(start, end) in &m.spans {
+            let text_ok =
+                m.matched_text.is_char_boundary(start) && m.matched_text.is_char_boundary(end);
+            if !color || start < pos || !text_ok {
+                continue;
+            }
+            text.push_str(&m.matched_text[pos..start]);
+            text.push_str(&paint(HIT, &m.matched_text[start..end]));
+            pos = end;
         }
+        text.push_str(&m.matched_text[pos..]);
 
-        let mut buffer = Vec::new();
-        if entry.read_to_end(&mut buffer).is_ok() {
-            let matches = scan_buffer(
-                &buffer,
-                &path.display().to_string(),
-                Some(entry_path),
-                pattern,
-            );
-            all_matches.extend(matches);
+        for c in &m.context_before {
+            context_line(&m.file_path, c);
+        }
+        println!(
+            "{}:{}: {}",
+            paint(PATH, &m.file_path),
+            paint(LINE, &line_number.to_string()),
+            text
+        );
+        for c in &m.context_after {
+            context_line(&m.file_path, c);
         }
+
+        let end = m
+            .context_after
+            .last()
+            .map(|c| c.line_number)
+            .unwrap_or(line_number);
+        last = Some((&m.file_path, end));
     }
-    Ok(all_matches)
 }
@@ -0,0 +1,151 @@
+use crate::config::Config;
+use crate::engine::{project, status};
+use anyhow::Result;
+use git2::Repository;
+use ignore::WalkBuilder;
+use rayon::prelude::*;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Activity summary of a top-level project
+#[derive(Debug, Clone, Serialize)]
+pub struct StaleProject {
+    pub name: String,
+    pub path: PathBuf,
+    /// Unix timestamp of the last commit on HEAD
+    pub last_commit: Option<i64>,
+    /// Unix timestamp of the newest non-ignored file
+    pub last_modified: Option<i64>,
+    /// Days since the later of the two
+    pub idle_days: i64,
+    pub is_repo: bool,
+    pub is_dirty: bool,
+    /// Commits or branches that are not on any remote, including repos that
+    /// were never pushed
+    pub has_unpushed: bool,
+}
+
+impl StaleProject {
+    /// Safe to archive: nothing would be lost that isn't on a remote
+    /// (`RepoStatus::is_fully_saved`)
+    pub fn is_clean(&self) -> bool {
+        !self.is_dirty && !self.has_unpushed
+    }
+}
+
+/// Projects ranked by staleness, most idle first
+#[derive(Debug, Default)]
+pub struct StaleReport {
+    pub projects: Vec<StaleProject>,
+    pub projects_dir_missing: bool,
+}
+
+impl StaleReport {
+    /// Projects idle for at least `days`
+    pub fn stale(&self, days: i64) -> impl Iterator<Item = &StaleProject> {
+        self.projects.iter().filter(move |p| p.idle_days >= days)
+    }
+}
+
+/// Rank every top-level project (except the CTF root) by time since its last
+/// commit or file change.
+pub fn find_stale(config: &Config) -> Result<StaleReport> {
+    if !config.resolve_path("projects").exists() {
+        return Ok(StaleReport {
+            projects_dir_missing: true,
+            ..Default::default()
+        });
+    }
+
+    let ctf_root = config.ctf_root();
+    let now = chrono::Utc::now().timestamp();
+    let mut projects: Vec<StaleProject> = project::list_projects(config)?
+        .into_par_iter()
+        .filter(|p| p.path != ctf_root)
+        .map(|p| analyze_project(p.name, p.path, now))
+        .collect();
+
+    projects.sort_by(|a, b| b.idle_days.cmp(&a.idle_days).then(a.name.cmp(&b.name)));
+    Ok(StaleReport {
+        projects,
+        projects_dir_missing: false,
+    })
+}
+
+fn analyze_project(name: String, path: PathBuf, now: i64) -> StaleProject {
+    let last_commit = last_commit_time(&path);
+    let last_modified = newest_mtime(&path);
+    let last_activity = last_commit.max(last_modified).unwrap_or(0);
+
+    let repo = path
+        .join(".git")
+        .exists()
+        .then(|| status::analyze_repo(&path).ok())
+        .flatten();
+
+    StaleProject {
+        name,
+        last_commit,
+        last_modified,
+        idle_days: (now - last_activity).max(0) / 86400,
+        is_repo: repo.is_some(),
+        is_dirty: repo.as_ref().is_some_and(|r| r.is_dirty),
+        has_unpushed: repo.as_ref().is_some_and(|r| r.has_local_only_work()),
+        path,
+    }
+}
+
+pub(crate) fn last_commit_time(path: &Path) -> Option<i64> {
+    let repo = Repository::open(path).ok()?;
+    let commit = repo.head().ok()?.peel_to_commit().ok()?;
+    Some(commit.time().seconds())
+}
+
+/// Newest mtime among entries not hidden by .gitignore, skipping `.git`.
+/// Directories count too, so deleting a file is activity.
+fn newest_mtime(path: &Path) -> Option<i64> {
+    WalkBuilder::new(path)
+        .hidden(false)
+        .filter_entry(|e| e.file_name() != ".git")
+        .build()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.metadata().ok()?.modified().ok())
+        .filter_map(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64)
+        .max()
+}
+
+/// Print projects as a table with idle time and save state
+pub fn print_stale_projects(projects: &[&StaleProject]) {
+    let date = |ts: Option<i64>| {
+        ts.and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| "-".to_string())
+    };
+
+    println!(
+        "\n{:<25} {:>6} {:<12} {:<12} State",
+        "Project", "Idle", "Last commit", "Last change"
+    );
+    println!("{}", "-".repeat(80));
+    for p in projects {
+        let state = if !p.is_repo {
+            "No repo"
+        } else if p.is_dirty {
+            "⚠ Dirty"
+        } else if p.has_unpushed {
+            "↑ Unpushed"
+        } else {
+            "✓ Clean"
+        };
+        println!(
+            "{:<25} {:>5}d {:<12} {:<12} {}",
+            p.name,
+            p.idle_days,
+            date(p.last_commit),
+            date(p.last_modified),
+            state
+        );
+    }
+}
@@ -1,8 +1,22 @@
 use crate::config::Config;
-use anyhow::Result;
-use ignore::WalkBuilder;
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use crate::engine::ctf;
+use crate::engine::project::{self, ProjectFilter};
+use crate::utils::output::human_size;
+use anyhow::{Context, Result};
+use ignore::{DirEntry, ParallelVisitor, ParallelVisitorBuilder, WalkBuilder, WalkState};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// PARA folders sizes are broken down by
+pub const AREA_KEYS: &[&str] = &["inbox", "projects", "areas", "resources", "archives"];
+
+/// How many of the biggest files `get_stats` keeps
+const LARGEST_FILES: usize = 20;
 
 #[derive(Debug, Default, Clone)]
 pub struct WorkspaceStats {
@@ -11,120 +25,787 @@ pub struct WorkspaceStats {
     pub total_files: usize,
     pub total_size_bytes: u64,
     pub file_types: HashMap<String, usize>,
+    /// CTF events as listed by `ctf list` (including year subfolders)
     pub ctf_count: usize,
+    pub ctf_challenges: usize,
+    /// Challenges with a saved flag
     pub ctf_solved: usize,
+    /// Projects per manifest status ("none" for projects without one)
+    pub projects_by_status: HashMap<String, usize>,
+    /// How many projects carry each manifest tag
+    pub project_tags: HashMap<String, usize>,
+    /// Bytes per PARA folder key (see `AREA_KEYS`)
+    pub area_sizes: HashMap<String, u64>,
+    /// Bytes per top-level project directory
+    pub project_sizes: HashMap<String, u64>,
+    /// Bytes per file extension
+    pub file_type_sizes: HashMap<String, u64>,
+    /// Biggest files seen, largest first
+    pub largest_files: Vec<FileSize>,
 }
 
-pub fn get_stats(config: &Config) -> Result<WorkspaceStats> {
-    let workspace = config.resolve_path("workspace");
+#[derive(Debug, Clone, Serialize)]
+pub struct FileSize {
+    pub path: PathBuf,
+    pub size_bytes: u64,
+}
 
-    // Quick scan using parallel iterator where possible
-    // Note: To be fully accurate and fast, we might want to just scan the projects dir
-    // but the user asked for "Workspace analytics".
+/// Recursive size of a directory, with its largest subdirectories
+#[derive(Debug, Clone, Serialize)]
+pub struct SizeNode {
+    pub name: String,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    pub file_count: usize,
+    pub children: Vec<SizeNode>,
+    /// Subdirectories beyond the `top` shown, and their combined size
+    pub hidden_children: usize,
+    pub hidden_bytes: u64,
+}
 
-    let mut stats = WorkspaceStats::default();
+/// Result of `size_tree`
+#[derive(Debug, Clone, Serialize)]
+pub struct SizeTreeReport {
+    pub tree: SizeNode,
+    pub largest_files: Vec<FileSize>,
+}
 
-    if !workspace.exists() {
-        return Ok(stats);
+/// Walk `roots` honouring .gitignore/.ignore unless `include_ignored`.
+/// Hidden entries (and so `.git`) are always skipped.
+fn build_walker(roots: &[PathBuf], include_ignored: bool) -> Option<WalkBuilder> {
+    let (first, rest) = roots.split_first()?;
+    let mut builder = WalkBuilder::new(first);
+    for root in rest {
+        builder.add(root);
     }
+    builder
+        .follow_links(false)
+        .ignore(!include_ignored)
+        .git_ignore(!include_ignored)
+        .git_exclude(!include_ignored)
+        .git_global(!include_ignored);
+    Some(builder)
+}
 
-    // Count projects (top-level folders in 1_Projects)
-    let projects_dir = config.resolve_path("projects");
-    if projects_dir.exists() {
-        if let Ok(entries) = fs_err::read_dir(&projects_dir) {
-            stats.total_projects = entries
-                .filter(|e| e.as_ref().map(|x| x.path().is_dir()).unwrap_or(false))
-                .count();
+/// Keeps the `limit` largest files pushed into it
+struct LargestFiles {
+    heap: BinaryHeap<Reverse<(u64, PathBuf)>>,
+    limit: usize,
+}
+
+impl LargestFiles {
+    fn new(limit: usize) -> Self {
+        LargestFiles {
+            heap: BinaryHeap::with_capacity(limit + 1),
+            limit,
+        }
+    }
+
+    fn push(&mut self, path: &Path, size: u64) {
+        if self.heap.len() == self.limit
+            && self
+                .heap
+                .peek()
+                .is_some_and(|Reverse((min, _))| size <= *min)
+        {
+            return;
         }
+        self.push_owned(path.to_path_buf(), size);
     }
 
-    // Count CTFs
-    let ctf_root = config.ctf_root();
-    if ctf_root.exists() {
-        // Using existing ctf module logic would be better but for speed let's just count
-        // We might need to handle this smarter to avoid circular deps if we use ctf module
-        // But ctf module is engine sibling so it's fine.
-        // Actually, let's keep it simple and just count dirs in ctf_root
-        if let Ok(entries) = fs_err::read_dir(&ctf_root) {
-            stats.ctf_count = entries
-                .filter(|e| e.as_ref().map(|x| x.path().is_dir()).unwrap_or(false))
-                .count();
+    fn push_owned(&mut self, path: PathBuf, size: u64) {
+        self.heap.push(Reverse((size, path)));
+        if self.heap.len() > self.limit {
+            self.heap.pop();
         }
     }
 
-    // Deep scan for files and repos
-    // This can be slow, so we should limit depth or confirm user intent?
-    // "wardex stats" usually implies a comprehensive scan.
-    // We can use WalkDir but parallelize accumulation?
+    fn merge(&mut self, other: LargestFiles) {
+        for Reverse((size, path)) in other.heap {
+            self.push_owned(path, size);
+        }
+    }
 
-    let walker = WalkBuilder::new(&workspace)
-        .max_depth(Some(10))
-        .follow_links(false)
-        .build();
+    fn into_sorted(self) -> Vec<FileSize> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((size_bytes, path))| FileSize { path, size_bytes })
+            .collect()
+    }
+}
 
-    let stats_mutex = Arc::new(Mutex::new((0u64, 0usize, HashMap::new())));
-    let repos_count = Arc::new(Mutex::new(0usize));
+/// File totals gathered by one walker thread, merged when it finishes
+struct ScanTotals {
+    size: u64,
+    files: usize,
+    repos: usize,
+    file_types: HashMap<String, usize>,
+    file_type_sizes: HashMap<String, u64>,
+    area_sizes: HashMap<String, u64>,
+    project_sizes: HashMap<String, u64>,
+    largest: LargestFiles,
+}
 
-    for entry in walker.filter_map(|e| e.ok()) {
+impl ScanTotals {
+    fn new() -> Self {
+        ScanTotals {
+            size: 0,
+            files: 0,
+            repos: 0,
+            file_types: HashMap::new(),
+            file_type_sizes: HashMap::new(),
+            area_sizes: HashMap::new(),
+            project_sizes: HashMap::new(),
+            largest: LargestFiles::new(LARGEST_FILES),
+        }
+    }
+
+    fn merge(&mut self, other: ScanTotals) {
+        fn add<V: std::ops::AddAssign + Default>(
+            into: &mut HashMap<String, V>,
+            from: HashMap<String, V>,
+        ) {
+            for (key, value) in from {
+                *into.entry(key).or_default() += value;
+            }
+        }
+
+        self.size += other.size;
+        self.files += other.files;
+        self.repos += other.repos;
+        add(&mut self.file_types, other.file_types);
+        add(&mut self.file_type_sizes, other.file_type_sizes);
+        add(&mut self.area_sizes, other.area_sizes);
+        add(&mut self.project_sizes, other.project_sizes);
+        self.largest.merge(other.largest);
+    }
+}
+
+/// Where files are attributed to, shared by all walker threads
+struct ScanLayout {
+    areas: Vec<(&'static str, PathBuf)>,
+    projects_dir: PathBuf,
+}
+
+struct StatsVisitorBuilder<'s> {
+    layout: &'s ScanLayout,
+    totals: &'s Mutex<ScanTotals>,
+}
+
+impl<'s> ParallelVisitorBuilder<'s> for StatsVisitorBuilder<'s> {
+    fn build(&mut self) -> Box<dyn ParallelVisitor + 's> {
+        Box::new(StatsVisitor {
+            layout: self.layout,
+            sink: self.totals,
+            local: Some(ScanTotals::new()),
+        })
+    }
+}
+
+/// Accumulates into `local` without locking; merges into `sink` on drop
+struct StatsVisitor<'s> {
+    layout: &'s ScanLayout,
+    sink: &'s Mutex<ScanTotals>,
+    local: Option<ScanTotals>,
+}
+
+impl StatsVisitor<'_> {
+    fn add_file(&mut self, entry: &DirEntry) {
+        let Some(totals) = self.local.as_mut() else {
+            return;
+        };
+        let Ok(metadata) = entry.metadata() else {
+            return;
+        };
         let path = entry.path();
+        let size = metadata.len();
 
-        if path.is_file() {
-            if let Ok(metadata) = entry.metadata() {
-                let size = metadata.len();
-                let ext = path.extension().and_then(|s| s.to_str()).map(String::from);
+        totals.size += size;
+        totals.files += 1;
+        if let Some(ext) = path.extension().and_then(|s| s.to_str()) {
+            *totals.file_types.entry(ext.to_string()).or_insert(0) += 1;
+            *totals.file_type_sizes.entry(ext.to_string()).or_insert(0) += size;
+        }
+        if let Some(area) = area_of(&self.layout.areas, path) {
+            *totals.area_sizes.entry(area.to_string()).or_insert(0) += size;
+        }
+        if let Some(project) = top_level_dir(&self.layout.projects_dir, path) {
+            *totals.project_sizes.entry(project).or_insert(0) += size;
+        }
+        totals.largest.push(path, size);
+    }
+}
 
-                let mut guard = stats_mutex.lock().unwrap();
-                guard.0 += size;
-                guard.1 += 1;
-                if let Some(ext) = ext {
-                    *guard.2.entry(ext).or_insert(0) += 1;
+impl ParallelVisitor for StatsVisitor<'_> {
+    fn visit(&mut self, entry: Result<DirEntry, ignore::Error>) -> WalkState {
+        let Ok(entry) = entry else {
+            return WalkState::Continue;
+        };
+        match entry.file_type() {
+            Some(ft) if ft.is_file() => self.add_file(&entry),
+            Some(ft) if ft.is_dir() && entry.path().join(".git").exists() => {
+                if let Some(totals) = self.local.as_mut() {
+                    totals.repos += 1;
                 }
             }
-        } else if path.is_dir() {
-            if path.join(".git").exists() {
-                let mut repos = repos_count.lock().unwrap();
-                *repos += 1;
+            _ => {}
+        }
+        WalkState::Continue
+    }
+}
+
+impl Drop for StatsVisitor<'_> {
+    fn drop(&mut self) {
+        if let Some(local) = self.local.take() {
+            if let Ok(mut totals) = self.sink.lock() {
+                totals.merge(local);
             }
         }
     }
+}
+
+/// Compute workspace analytics. A non-empty `filter` restricts project counts
+/// and the file scan to the matching projects; `include_ignored` also counts
+/// files hidden by .gitignore (build output, dependencies).
+pub fn get_stats(
+    config: &Config,
+    filter: &ProjectFilter,
+    include_ignored: bool,
+) -> Result<WorkspaceStats> {
+    let workspace = config.resolve_path("workspace");
+    let mut stats = WorkspaceStats::default();
+
+    if !workspace.exists() {
+        return Ok(stats);
+    }
+
+    // Count projects (top-level folders in 1_Projects)
+    let projects: Vec<_> = project::list_projects(config)?
+        .into_iter()
+        .filter(|p| filter.matches(p.manifest.as_ref()))
+        .collect();
+    stats.total_projects = projects.len();
+    for p in &projects {
+        let status = p
+            .manifest
+            .as_ref()
+            .and_then(|m| m.status)
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "none".to_string());
+        *stats.projects_by_status.entry(status).or_insert(0) += 1;
+        for tag in p.manifest.iter().flat_map(|m| &m.tags) {
+            *stats.project_tags.entry(tag.clone()).or_insert(0) += 1;
+        }
+    }
+
+    let events = ctf::list_events(config)?.events;
+    stats.ctf_count = events.len();
+    stats.ctf_challenges = events.iter().map(|e| e.challenge_count).sum();
+    stats.ctf_solved = events.iter().map(|e| e.solved_count).sum();
+
+    let roots: Vec<_> = if filter.is_empty() {
+        vec![workspace]
+    } else {
+        projects.iter().map(|p| p.path.clone()).collect()
+    };
+    let Some(builder) = build_walker(&roots, include_ignored) else {
+        return Ok(stats);
+    };
 
-    let (total_size, total_files, file_types) =
-        Arc::try_unwrap(stats_mutex).unwrap().into_inner().unwrap();
-    let total_repos = Arc::try_unwrap(repos_count).unwrap().into_inner().unwrap();
+    let layout = ScanLayout {
+        areas: AREA_KEYS
+            .iter()
+            .map(|key| (*key, config.resolve_path(key)))
+            .collect(),
+        projects_dir: config.resolve_path("projects"),
+    };
+    let totals = Mutex::new(ScanTotals::new());
+    builder.build_parallel().visit(&mut StatsVisitorBuilder {
+        layout: &layout,
+        totals: &totals,
+    });
+    let totals = totals
+        .into_inner()
+        .map_err(|_| anyhow::anyhow!("Stats scan panicked"))?;
 
-    stats.total_size_bytes = total_size;
-    stats.total_files = total_files;
-    stats.file_types = file_types;
-    stats.total_repos = total_repos;
+    stats.total_size_bytes = totals.size;
+    stats.total_files = totals.files;
+    stats.total_repos = totals.repos;
+    stats.file_types = totals.file_types;
+    stats.file_type_sizes = totals.file_type_sizes;
+    stats.area_sizes = totals.area_sizes;
+    stats.project_sizes = totals.project_sizes;
+    stats.largest_files = totals.largest.into_sorted();
 
     Ok(stats)
 }
 
-pub fn print_stats(stats: &WorkspaceStats) {
+/// du-style breakdown of `root`: every directory's recursive size, `depth`
+/// levels deep with the `top` largest subdirectories per level, plus the
+/// `top` largest files anywhere below `root`.
+pub fn size_tree(
+    root: &Path,
+    depth: usize,
+    top: usize,
+    include_ignored: bool,
+) -> Result<SizeTreeReport> {
+    let root = fs_err::canonicalize(root)?;
+    // Recursive (bytes, files) per directory below and including root
+    let mut dirs: HashMap<PathBuf, (u64, usize)> = HashMap::new();
+    let mut largest = LargestFiles::new(top);
+
+    let walker = build_walker(std::slice::from_ref(&root), include_ignored)
+        .context("Nothing to scan")?
+        .build();
+    for entry in walker.filter_map(|e| e.ok()) {
+        let Some(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            dirs.entry(entry.path().to_path_buf()).or_default();
+            continue;
+        }
+        if !file_type.is_file() {
+            continue;
+        }
+        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        largest.push(entry.path(), size);
+        for dir in entry.path().ancestors().skip(1) {
+            let totals = dirs.entry(dir.to_path_buf()).or_default();
+            totals.0 += size;
+            totals.1 += 1;
+            if dir == root {
+                break;
+            }
+        }
+    }
+
+    let mut children: HashMap<&Path, Vec<&Path>> = HashMap::new();
+    for dir in dirs.keys() {
+        if dir != &root {
+            if let Some(parent) = dir.parent() {
+                children.entry(parent).or_default().push(dir);
+            }
+        }
+    }
+
+    let tree = size_node(&root, &dirs, &children, depth, top);
+    Ok(SizeTreeReport {
+        tree,
+        largest_files: largest.into_sorted(),
+    })
+}
+
+fn size_node(
+    dir: &Path,
+    dirs: &HashMap<PathBuf, (u64, usize)>,
+    children: &HashMap<&Path, Vec<&Path>>,
+    depth: usize,
+    top: usize,
+) -> SizeNode {
+    let (size_bytes, file_count) = dirs.get(dir).copied().unwrap_or_default();
+    let mut node = SizeNode {
+        name: dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| dir.display().to_string()),
+        path: dir.to_path_buf(),
+        size_bytes,
+        file_count,
+        children: Vec::new(),
+        hidden_children: 0,
+        hidden_bytes: 0,
+    };
+    if depth == 0 {
+        return node;
+    }
+
+    let mut subdirs: Vec<&Path> = children.get(dir).cloned().unwrap_or_default();
+    let size = |d: &Path| dirs.get(d).map(|t| t.0).unwrap_or(0);
+    subdirs.sort_by(|a, b| size(b).cmp(&size(a)).then_with(|| a.cmp(b)));
+    for (i, sub) in subdirs.into_iter().enumerate() {
+        if i < top {
+            node.children
+                .push(size_node(sub, dirs, children, depth - 1, top));
+        } else {
+            node.hidden_children += 1;
+            node.hidden_bytes += size(sub);
+        }
+    }
+    node
+}
+
+/// Point-in-time copy of the workspace totals, one line of the history log
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StatsSnapshot {
+    /// Unix timestamp
+    pub timestamp: i64,
+    pub total_projects: usize,
+    pub total_repos: usize,
+    pub total_files: usize,
+    pub total_size_bytes: u64,
+    #[serde(default)]
+    pub area_sizes: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub project_sizes: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub file_types: BTreeMap<String, usize>,
+    pub ctf_count: usize,
+    #[serde(default)]
+    pub ctf_challenges: usize,
+    pub ctf_solved: usize,
+}
+
+impl StatsSnapshot {
+    pub fn from_stats(stats: &WorkspaceStats) -> Self {
+        StatsSnapshot {
+            timestamp: chrono::Utc::now().timestamp(),
+            total_projects: stats.total_projects,
+            total_repos: stats.total_repos,
+            total_files: stats.total_files,
+            total_size_bytes: stats.total_size_bytes,
+            area_sizes: stats.area_sizes.clone().into_iter().collect(),
+            project_sizes: stats.project_sizes.clone().into_iter().collect(),
+            file_types: stats.file_types.clone().into_iter().collect(),
+            ctf_count: stats.ctf_count,
+            ctf_challenges: stats.ctf_challenges,
+            ctf_solved: stats.ctf_solved,
+        }
+    }
+}
+
+/// Size change of one project between the oldest and newest snapshot
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectGrowth {
+    pub name: String,
+    pub first_bytes: u64,
+    pub last_bytes: u64,
+    pub delta_bytes: i64,
+}
+
+/// Size of one project at the time of a snapshot
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectPoint {
+    pub timestamp: i64,
+    pub size_bytes: u64,
+}
+
+/// Size of project `name` in every snapshot, zero where it didn't exist
+pub fn project_series(snapshots: &[StatsSnapshot], name: &str) -> Vec<ProjectPoint> {
+    snapshots
+        .iter()
+        .map(|s| ProjectPoint {
+            timestamp: s.timestamp,
+            size_bytes: s.project_sizes.get(name).copied().unwrap_or(0),
+        })
+        .collect()
+}
+
+fn get_history_path(config: &Config) -> PathBuf {
+    config
+        .resolve_path("workspace")
+        .join(".stats_history.jsonl")
+}
+
+/// Append a snapshot of `stats` to the workspace history log
+pub fn record_snapshot(config: &Config, stats: &WorkspaceStats) -> Result<StatsSnapshot> {
+    let snapshot = StatsSnapshot::from_stats(stats);
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(get_history_path(config))
+        .context("Failed to open stats history")?;
+    writeln!(file, "{}", serde_json::to_string(&snapshot)?)?;
+    Ok(snapshot)
+}
+
+/// Recorded snapshots, oldest first. Unreadable lines are skipped.
+pub fn load_history(config: &Config) -> Result<Vec<StatsSnapshot>> {
+    let path = get_history_path(config);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let mut snapshots: Vec<StatsSnapshot> = fs_err::read_to_string(&path)?
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    snapshots.sort_by_key(|s| s.timestamp);
+    Ok(snapshots)
+}
+
+/// Projects whose size changed between the first and last snapshot, largest
+/// change first. Projects that appeared or vanished count from/to zero.
+pub fn project_growth(snapshots: &[StatsSnapshot]) -> Vec<ProjectGrowth> {
+    let (Some(first), Some(last)) = (snapshots.first(), snapshots.last()) else {
+        return Vec::new();
+    };
+    let names: std::collections::BTreeSet<&String> = first
+        .project_sizes
+        .keys()
+        .chain(last.project_sizes.keys())
+        .collect();
+
+    let mut growth: Vec<ProjectGrowth> = names
+        .into_iter()
+        .map(|name| {
+            let first_bytes = first.project_sizes.get(name).copied().unwrap_or(0);
+            let last_bytes = last.project_sizes.get(name).copied().unwrap_or(0);
+            ProjectGrowth {
+                name: name.clone(),
+                first_bytes,
+                last_bytes,
+                delta_bytes: last_bytes as i64 - first_bytes as i64,
+            }
+        })
+        .filter(|g| g.delta_bytes != 0)
+        .collect();
+    growth.sort_by(|a, b| {
+        b.delta_bytes
+            .abs()
+            .cmp(&a.delta_bytes.abs())
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    growth
+}
+
+/// The PARA folder containing `path`; the deepest wins if folders are nested
+fn area_of(areas: &[(&'static str, PathBuf)], path: &Path) -> Option<&'static str> {
+    areas
+        .iter()
+        .filter(|(_, root)| path.starts_with(root))
+        .max_by_key(|(_, root)| root.components().count())
+        .map(|(key, _)| *key)
+}
+
+/// Name of the directory directly below `root` that contains `path`
+fn top_level_dir(root: &Path, path: &Path) -> Option<String> {
+    let mut components = path.strip_prefix(root).ok()?.components();
+    let first = components.next()?;
+    // A file directly in `root` isn't inside a project
+    components.next()?;
+    Some(first.as_os_str().to_string_lossy().to_string())
+}
+
+/// Print the analytics, showing at most `top` rows per breakdown
+pub fn print_stats(stats: &WorkspaceStats, workspace: &Path, top: usize) {
     println!("📊 Workspace Analytics");
     println!("{}", "-".repeat(40));
     println!("Projects:    {}", stats.total_projects);
     println!("Git Repos:   {}", stats.total_repos);
-    println!("CTF Events:  {}", stats.ctf_count);
-    println!("Total Files: {}", stats.total_files);
     println!(
-        "Total Size:  {:.2} MB",
-        stats.total_size_bytes as f64 / 1024.0 / 1024.0
+        "CTF Events:  {} ({}/{} challenges solved)",
+        stats.ctf_count, stats.ctf_solved, stats.ctf_challenges
     );
+    println!("Total Files: {}", stats.total_files);
+    println!("Total Size:  {}", human_size(stats.total_size_bytes));
+
+    let share = |bytes: u64| {
+        if stats.total_size_bytes == 0 {
+            0.0
+        } else {
+            bytes as f64 * 100.0 / stats.total_size_bytes as f64
+        }
+    };
+
+    if !stats.area_sizes.is_empty() {
+        println!("\n🗂️  Size by Area");
+        for area in AREA_KEYS {
+            if let Some(bytes) = stats.area_sizes.get(*area) {
+                println!(
+                    "  {:<10} {:>10} {:>5.1}%",
+                    area,
+                    human_size(*bytes),
+                    share(*bytes)
+                );
+            }
+        }
+    }
+
+    if !stats.project_sizes.is_empty() {
+        println!("\n📦 Largest Projects");
+        let mut projects: Vec<_> = stats.project_sizes.iter().collect();
+        projects.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        for (name, bytes) in projects.iter().take(top) {
+            println!(
+                "  {:<25} {:>10} {:>5.1}%",
+                name,
+                human_size(**bytes),
+                share(**bytes)
+            );
+        }
+    }
 
     println!("\n📁 Top File Types");
     let mut sorted_types: Vec<_> = stats.file_types.iter().collect();
-    sorted_types.sort_by(|a, b| b.1.cmp(a.1));
+    sorted_types.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
 
-    for (ext, count) in sorted_types.iter().take(5) {
-        println!("  .{:<4} : {}", ext, count);
+    for (ext, count) in sorted_types.iter().take(top) {
+        let bytes = stats.file_type_sizes.get(*ext).copied().unwrap_or(0);
+        println!("  .{:<8} {:>7} files {:>10}", ext, count, human_size(bytes));
     }
+
+    if !stats.largest_files.is_empty() {
+        println!("\n📄 Largest Files");
+        for file in stats.largest_files.iter().take(top) {
+            let rel = file.path.strip_prefix(workspace).unwrap_or(&file.path);
+            println!("  {:>10}  {}", human_size(file.size_bytes), rel.display());
+        }
+    }
+
+    if stats.projects_by_status.keys().any(|s| s != "none") || !stats.project_tags.is_empty() {
+        println!("\n🏷️  Projects by Status");
+        let mut statuses: Vec<_> = stats.projects_by_status.iter().collect();
+        statuses.sort();
+        for (status, count) in statuses {
+            println!("  {:<8} : {}", status, count);
+        }
+
+        let mut tags: Vec<_> = stats.project_tags.iter().collect();
+        tags.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        if !tags.is_empty() {
+            println!("\n🔖 Top Tags");
+            for (tag, count) in tags.iter().take(5) {
+                println!("  {:<12} : {}", tag, count);
+            }
+        }
+    }
+}
+
+/// Print a `size_tree` report as an indented tree
+pub fn print_size_tree(report: &SizeTreeReport) {
+    fn print_node(node: &SizeNode, prefix: &str, is_last: bool, is_root: bool) {
+        if is_root {
+            println!(
+                "{:>10}  {} ({} files)",
+                human_size(node.size_bytes),
+                node.path.display(),
+                node.file_count
+            );
+        } else {
+            let branch = if is_last { "└── " } else { "├── " };
+            println!(
+                "{:>10}  {}{}{}/",
+                human_size(node.size_bytes),
+                prefix,
+                branch,
+                node.name
+            );
+        }
+
+        let child_prefix = if is_root {
+            String::new()
+        } else {
+            format!("{}{}", prefix, if is_last { "    " } else { "│   " })
+        };
+        for (i, child) in node.children.iter().enumerate() {
+            let last = i + 1 == node.children.len() && node.hidden_children == 0;
+            print_node(child, &child_prefix, last, false);
+        }
+        if node.hidden_children > 0 {
+            println!(
+                "{:>10}  {}└── … {} more",
+                human_size(node.hidden_bytes),
+                child_prefix,
+                node.hidden_children
+            );
+        }
+    }
+
+    print_node(&report.tree, "", true, true);
+
+    if !report.largest_files.is_empty() {
+        println!("\n📄 Largest Files");
+        for file in &report.largest_files {
+            let rel = file
+                .path
+                .strip_prefix(&report.tree.path)
+                .unwrap_or(&file.path);
+            println!("  {:>10}  {}", human_size(file.size_bytes), rel.display());
+        }
+    }
+}
+
+/// Print snapshots as a table of area sizes, then the fastest-growing projects
+pub fn print_history(snapshots: &[StatsSnapshot]) {
+    print!("\n{:<17} {:>10} {:>8}", "Date", "Total", "Files");
+    for area in AREA_KEYS {
+        print!(" {:>10}", area);
+    }
+    println!(" {:>6}", "Solved");
+    println!("{}", "-".repeat(96));
+    for s in snapshots {
+        print!(
+            "{:<17} {:>10} {:>8}",
+            snapshot_time(s.timestamp),
+            human_size(s.total_size_bytes),
+            s.total_files
+        );
+        for area in AREA_KEYS {
+            let bytes = s.area_sizes.get(*area).copied().unwrap_or(0);
+            print!(" {:>10}", human_size(bytes));
+        }
+        println!(" {:>6}", s.ctf_solved);
+    }
+
+    let growth = project_growth(snapshots);
+    if let (Some(first), false) = (snapshots.first(), growth.is_empty()) {
+        println!(
+            "\n📈 Project growth since {}",
+            snapshot_time(first.timestamp)
+        );
+        for g in growth.iter().take(10) {
+            println!(
+                "  {:<25} {:>10} → {:>10}  ({})",
+                g.name,
+                human_size(g.first_bytes),
+                human_size(g.last_bytes),
+                signed_size(g.delta_bytes)
+            );
+        }
+    }
+}
+
+/// Print one project's size at each snapshot and the change since the previous
+pub fn print_project_history(name: &str, series: &[ProjectPoint]) {
+    println!("\n📈 {}", name);
+    println!("{:<17} {:>10} {:>12}", "Date", "Size", "Change");
+    println!("{}", "-".repeat(41));
+    let mut previous: Option<u64> = None;
+    for point in series {
+        let change = previous
+            .map(|p| signed_size(point.size_bytes as i64 - p as i64))
+            .unwrap_or_default();
+        println!(
+            "{:<17} {:>10} {:>12}",
+            snapshot_time(point.timestamp),
+            human_size(point.size_bytes),
+            change
+        );
+        previous = Some(point.size_bytes);
+    }
+}
+
+/// Local date and time of a stats snapshot
+fn snapshot_time(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .map(|d| {
+            d.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+/// Byte delta with an explicit sign, e.g. `+1.5 MB`
+fn signed_size(delta: i64) -> String {
+    let sign = if delta < 0 { "-" } else { "+" };
+    format!("{}{}", sign, human_size(delta.unsigned_abs()))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tempfile::TempDir;
 
     #[test]
     fn test_workspace_stats_default() {
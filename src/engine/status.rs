@@ -1,21 +1,86 @@
 use crate::config::Config;
+use crate::engine::index;
+use crate::utils::output;
 use anyhow::Result;
-use git2::{Repository, StatusOptions};
+use clap::ValueEnum;
+use git2::{BranchType, Repository, RepositoryState, Status, StatusOptions};
 use ignore::WalkBuilder;
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashMap};
+use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
 
 /// Status of a single git repository
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct RepoStatus {
     pub name: String,
     pub path: PathBuf,
     pub is_dirty: bool,
     pub sync_status: SyncStatus,
+    /// Current branch, `None` when detached or unborn
+    pub branch: Option<String>,
+    pub staged: usize,
+    pub modified: usize,
+    pub untracked: usize,
+    pub conflicted: usize,
+    pub stashes: usize,
+    pub last_commit: Option<CommitSummary>,
+    /// Local branches that have no upstream configured
+    pub branches_without_upstream: usize,
+    /// Operation left in progress, e.g. "rebase" or "merge"
+    pub operation: Option<String>,
+    pub kind: RepoKind,
+    /// Repository a submodule belongs to
+    pub parent: Option<PathBuf>,
+    /// Change counts were reused from the status cache
+    pub from_cache: bool,
+    /// Why the repository is not safe to leave, e.g. uncommitted changes
+    pub problems: Vec<String>,
+}
+
+/// How a repository is laid out on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RepoKind {
+    /// Work tree with a `.git` directory
+    Normal,
+    /// Linked worktree, whose `.git` is a file pointing at the main repository
+    Worktree,
+    /// Bare repository without a work tree
+    Bare,
+    /// Submodule of another discovered repository
+    Submodule,
+}
+
+/// The commit HEAD points at
+#[derive(Debug, Clone, Serialize)]
+pub struct CommitSummary {
+    /// Unix timestamp (seconds)
+    pub time: i64,
+    pub author: String,
+    pub summary: String,
+}
+
+/// Orderings for `wardex status`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum StatusSort {
+    /// Repository name
+    #[default]
+    Name,
+    /// Full path
+    Path,
+    /// Most pending changes first
+    Changes,
+    /// Most recent commit first
+    Activity,
 }
 
 /// Sync status with remote
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SyncStatus {
     Synced,
     Ahead(usize),
@@ -42,14 +107,216 @@ impl SyncStatus {
     }
 }
 
+impl RepoStatus {
+    /// Commits that exist only locally (ahead of, or diverged from, upstream)
+    pub fn has_unpushed(&self) -> bool {
+        matches!(
+            self.sync_status,
+            SyncStatus::Ahead(_) | SyncStatus::Diverged { .. }
+        )
+    }
+
+    /// Work that exists only in this clone: unpushed commits, or branches
+    /// (including the current one) that were never pushed anywhere
+    pub fn has_local_only_work(&self) -> bool {
+        self.has_unpushed()
+            || matches!(self.sync_status, SyncStatus::Local)
+            || self.branches_without_upstream > 0
+    }
+
+    /// Everything is committed and on a remote, so the clone is safe to
+    /// archive or delete
+    pub fn is_fully_saved(&self) -> bool {
+        !self.is_dirty && !self.has_local_only_work()
+    }
+
+    pub fn to_row(&self) -> RepoRow<'_> {
+        RepoRow {
+            name: &self.name,
+            path: &self.path,
+            kind: self.kind,
+            branch: self.branch.as_deref(),
+            sync: self.sync_status.display(),
+            staged: self.staged,
+            modified: self.modified,
+            untracked: self.untracked,
+            conflicted: self.conflicted,
+            stashes: self.stashes,
+            branches_without_upstream: self.branches_without_upstream,
+            last_commit: self.last_commit.as_ref().map(|c| c.time),
+            operation: self.operation.as_deref(),
+            problems: self.problems.join("; "),
+        }
+    }
+
+    /// Staged, modified, untracked and conflicted files together
+    pub fn pending_changes(&self) -> usize {
+        self.staged + self.modified + self.untracked + self.conflicted
+    }
+
+    /// Compact change counts like `+2 ~1 ?3 !1`, or `✓ Clean`
+    pub fn changes_summary(&self) -> String {
+        let parts: Vec<String> = [
+            ('+', self.staged),
+            ('~', self.modified),
+            ('?', self.untracked),
+            ('!', self.conflicted),
+        ]
+        .iter()
+        .filter(|(_, n)| *n > 0)
+        .map(|(c, n)| format!("{}{}", c, n))
+        .collect();
+        if parts.is_empty() {
+            "✓ Clean".to_string()
+        } else {
+            parts.join(" ")
+        }
+    }
+}
+
+/// A repository flattened for CSV output
+#[derive(Debug, Serialize)]
+pub struct RepoRow<'a> {
+    pub name: &'a str,
+    pub path: &'a Path,
+    pub kind: RepoKind,
+    pub branch: Option<&'a str>,
+    pub sync: String,
+    pub staged: usize,
+    pub modified: usize,
+    pub untracked: usize,
+    pub conflicted: usize,
+    pub stashes: usize,
+    pub branches_without_upstream: usize,
+    /// Unix timestamp of the commit HEAD points at
+    pub last_commit: Option<i64>,
+    pub operation: Option<&'a str>,
+    /// Semicolon-separated
+    pub problems: String,
+}
+
+/// Reasons the repository needs attention: uncommitted changes, commits
+/// not on the upstream, history diverged from it, or branches never pushed.
+/// Empty exactly when `is_fully_saved` holds.
+fn find_problems(status: &RepoStatus) -> Vec<String> {
+    let mut problems = Vec::new();
+    if status.is_dirty {
+        problems.push(format!(
+            "uncommitted changes ({})",
+            status.changes_summary()
+        ));
+    }
+    match status.sync_status {
+        SyncStatus::Ahead(n) => problems.push(format!("{} unpushed commits", n)),
+        SyncStatus::Diverged { ahead, behind } => problems.push(format!(
+            "diverged from upstream ({} ahead, {} behind)",
+            ahead, behind
+        )),
+        _ => {}
+    }
+    if status.branches_without_upstream > 0 {
+        problems.push(format!(
+            "{} branches without upstream",
+            status.branches_without_upstream
+        ));
+    } else if matches!(status.sync_status, SyncStatus::Local) {
+        problems.push("no upstream".to_string());
+    }
+    problems
+}
+
+/// Order repositories in place, keeping submodules right after their parent
+pub fn sort_repos(repos: &mut Vec<RepoStatus>, sort: StatusSort) {
+    match sort {
+        StatusSort::Name => repos.sort_by(|a, b| a.name.cmp(&b.name).then(a.path.cmp(&b.path))),
+        StatusSort::Path => repos.sort_by(|a, b| a.path.cmp(&b.path)),
+        StatusSort::Changes => repos.sort_by(|a, b| {
+            b.pending_changes()
+                .cmp(&a.pending_changes())
+                .then(a.name.cmp(&b.name))
+        }),
+        StatusSort::Activity => repos.sort_by_key(|r| {
+            std::cmp::Reverse(r.last_commit.as_ref().map(|c| c.time).unwrap_or(i64::MIN))
+        }),
+    }
+
+    if repos.iter().any(|r| r.parent.is_some()) {
+        let (children, mut ordered): (Vec<_>, Vec<_>) =
+            repos.drain(..).partition(|r| r.parent.is_some());
+        // Inserting in reverse right after the parent keeps the children sorted
+        for child in children.into_iter().rev() {
+            match ordered
+                .iter()
+                .position(|p| child.parent.as_ref() == Some(&p.path))
+            {
+                Some(i) => ordered.insert(i + 1, child),
+                None => ordered.push(child),
+            }
+        }
+        *repos = ordered;
+    }
+}
+
+/// Change counts from the last scan of each repository, reused while the
+/// index, HEAD and the tracked files and their directories are unchanged.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StatusCache {
+    repos: HashMap<PathBuf, CacheEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    key: CacheKey,
+    counts: ChangeCounts,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CacheKey {
+    index_mtime_ns: i64,
+    head: Option<String>,
+    /// Hash of the size and mtime of every tracked file and the mtime of
+    /// every directory holding one, so edits, deletions and new files in
+    /// those directories all invalidate the entry
+    worktree_stamp: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ChangeCounts {
+    staged: usize,
+    modified: usize,
+    untracked: usize,
+    conflicted: usize,
+}
+
+impl StatusCache {
+    fn load(config: &Config) -> Self {
+        index::workspace_cache_file(config, "status", "json")
+            .ok()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, config: &Config) -> Result<()> {
+        let path = index::workspace_cache_file(config, "status", "json")?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
+
 /// Result of status scan
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize)]
 pub struct StatusReport {
     pub repos: Vec<RepoStatus>,
     pub workspace_not_found: bool,
 }
 
-pub fn show_status(config: &Config) -> Result<StatusReport> {
+/// Scan the workspace for repositories. Change counts come from the status
+/// cache where a repository is unchanged, unless `refresh` is set.
+pub fn show_status(config: &Config, refresh: bool) -> Result<StatusReport> {
     let workspace = config.resolve_path("workspace");
 
     if !workspace.exists() {
@@ -59,51 +326,292 @@ pub fn show_status(config: &Config) -> Result<StatusReport> {
         });
     }
 
-    // Find all .git directories
-    let git_dirs: Vec<PathBuf> = WalkBuilder::new(&workspace)
-        .max_depth(Some(3))
+    // The walker skips hidden entries, so repositories are recognised from
+    // their own directory. Once inside one, don't descend any further, except
+    // below the workspace itself, which may be a repository too.
+    let repo_dirs: Vec<PathBuf> = WalkBuilder::new(&workspace)
+        .max_depth(Some(config.status.discovery_depth))
+        .filter_entry(|e| e.depth() <= 1 || !e.path().parent().is_some_and(is_repo_dir))
         .build()
         .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().map(|ft| ft.is_dir()).unwrap_or(false) && e.file_name() == ".git")
-        .filter_map(|e| e.path().parent().map(|p| p.to_path_buf()))
+        .filter(|e| e.file_type().is_some_and(|ft| ft.is_dir()) && is_repo_dir(e.path()))
+        .map(|e| e.path().to_path_buf())
         .collect();
 
-    let repos: Vec<RepoStatus> = git_dirs
+    let cache = if refresh {
+        StatusCache::default()
+    } else {
+        StatusCache::load(config)
+    };
+    let analyzed: Vec<(RepoStatus, Option<CacheEntry>)> = repo_dirs
         .par_iter()
-        .filter_map(|path| analyze_repo(path).ok())
+        .filter_map(|path| analyze_repo_cached(path, cache.repos.get(path)).ok())
         .collect();
 
+    let mut new_cache = StatusCache::default();
+    let mut repos = Vec::with_capacity(analyzed.len());
+    for (repo, entry) in analyzed {
+        if let Some(entry) = entry {
+            new_cache.repos.insert(repo.path.clone(), entry);
+        }
+        repos.push(repo);
+    }
+    if let Err(e) = new_cache.save(config) {
+        log::debug!("Could not save status cache: {:#}", e);
+    }
+
+    if config.status.submodules {
+        let submodules: Vec<RepoStatus> = repos
+            .par_iter()
+            .filter(|r| r.kind != RepoKind::Bare)
+            .flat_map(|r| analyze_submodules(&r.path))
+            .collect();
+        repos.extend(submodules);
+    }
+    sort_repos(&mut repos, StatusSort::Name);
+
     Ok(StatusReport {
         repos,
         workspace_not_found: false,
     })
 }
 
-fn analyze_repo(path: &Path) -> Result<RepoStatus> {
-    let repo = Repository::open(path)?;
+/// A work tree (`.git` directory or file) or a bare repository
+pub fn is_repo_dir(path: &Path) -> bool {
+    path.join(".git").exists()
+        || (path.join("HEAD").is_file()
+            && path.join("objects").is_dir()
+            && path.join("refs").is_dir())
+}
+
+/// Checked-out submodules of the repository at `path`
+fn analyze_submodules(path: &Path) -> Vec<RepoStatus> {
+    let Ok(repo) = Repository::open(path) else {
+        return Vec::new();
+    };
+    let Ok(submodules) = repo.submodules() else {
+        return Vec::new();
+    };
+    submodules
+        .iter()
+        .map(|sm| path.join(sm.path()))
+        .filter(|sm_path| sm_path.join(".git").exists())
+        .filter_map(|sm_path| analyze_repo(&sm_path).ok())
+        .map(|mut sm| {
+            sm.kind = RepoKind::Submodule;
+            sm.parent = Some(path.to_path_buf());
+            sm
+        })
+        .collect()
+}
+
+/// Working tree, branch and sync state of the repository at `path`
+pub fn analyze_repo(path: &Path) -> Result<RepoStatus> {
+    analyze_repo_cached(path, None).map(|(status, _)| status)
+}
+
+/// Like `analyze_repo`, reusing `cached` change counts when its key still
+/// matches. Also returns the cache entry for this scan (none for bare repos).
+fn analyze_repo_cached(
+    path: &Path,
+    cached: Option<&CacheEntry>,
+) -> Result<(RepoStatus, Option<CacheEntry>)> {
+    let mut repo = Repository::open(path)?;
     let name = path
         .file_name()
         .unwrap_or_default()
         .to_string_lossy()
         .to_string();
 
-    // Check dirty state
-    let mut opts = StatusOptions::new();
-    opts.include_untracked(true);
-    let statuses = repo.statuses(Some(&mut opts))?;
-    let is_dirty = !statuses.is_empty();
-
-    // Check sync status
-    let sync_status = get_sync_status(&repo);
-
-    Ok(RepoStatus {
+    let mut status = RepoStatus {
         name,
         path: path.to_path_buf(),
-        is_dirty,
-        sync_status,
+        is_dirty: false,
+        sync_status: get_sync_status(&repo),
+        branch: None,
+        staged: 0,
+        modified: 0,
+        untracked: 0,
+        conflicted: 0,
+        stashes: 0,
+        last_commit: None,
+        branches_without_upstream: 0,
+        operation: operation_in_progress(repo.state()),
+        kind: if repo.is_bare() {
+            RepoKind::Bare
+        } else if repo.is_worktree() {
+            RepoKind::Worktree
+        } else {
+            RepoKind::Normal
+        },
+        parent: None,
+        from_cache: false,
+        problems: Vec::new(),
+    };
+
+    // Bare repositories have no work tree to compare against
+    let entry = match cache_key(&repo) {
+        Some(key) => {
+            let counts = match cached.filter(|c| c.key == key) {
+                Some(c) => {
+                    status.from_cache = true;
+                    c.counts.clone()
+                }
+                None => count_changes(&repo)?,
+            };
+            Some(CacheEntry { key, counts })
+        }
+        None => None,
+    };
+    if let Some(CacheEntry { counts, .. }) = &entry {
+        status.staged = counts.staged;
+        status.modified = counts.modified;
+        status.untracked = counts.untracked;
+        status.conflicted = counts.conflicted;
+    }
+    status.is_dirty = status.pending_changes() > 0;
+
+    if let Ok(head) = repo.head() {
+        if head.is_branch() {
+            status.branch = head.shorthand().map(String::from);
+        }
+        if let Ok(commit) = head.peel_to_commit() {
+            status.last_commit = Some(CommitSummary {
+                time: commit.time().seconds(),
+                author: commit.author().name().unwrap_or("unknown").to_string(),
+                summary: commit.summary().unwrap_or("").to_string(),
+            });
+        }
+    } else if let Ok(head) = repo.find_reference("HEAD") {
+        // Unborn branch: HEAD is symbolic but points at nothing yet
+        status.branch = head
+            .symbolic_target()
+            .and_then(|t| t.strip_prefix("refs/heads/"))
+            .map(String::from);
+    }
+
+    if let Ok(branches) = repo.branches(Some(BranchType::Local)) {
+        status.branches_without_upstream = branches
+            .flatten()
+            .filter(|(branch, _)| branch.upstream().is_err())
+            .count();
+    }
+
+    repo.stash_foreach(|_, _, _| {
+        status.stashes += 1;
+        true
+    })?;
+
+    status.problems = find_problems(&status);
+    Ok((status, entry))
+}
+
+/// Index mtime, HEAD and work tree stamp. `None` for bare repositories.
+fn cache_key(repo: &Repository) -> Option<CacheKey> {
+    let workdir = repo.workdir()?;
+    Some(CacheKey {
+        index_mtime_ns: mtime_ns(&repo.path().join("index")),
+        head: repo
+            .head()
+            .ok()
+            .and_then(|h| h.target())
+            .map(|oid| oid.to_string()),
+        worktree_stamp: worktree_stamp(repo, workdir)?,
     })
 }
 
+fn mtime_ns(path: &Path) -> i64 {
+    std::fs::symlink_metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos() as i64)
+        .unwrap_or(0)
+}
+
+/// Stat every file in the index, like `git status` does before comparing
+/// contents, plus each directory on the way to one. Creating or removing an
+/// untracked file changes the mtime of its directory.
+fn worktree_stamp(repo: &Repository, workdir: &Path) -> Option<u64> {
+    let index = repo.index().ok()?;
+    let mut hasher = DefaultHasher::new();
+    let mut dirs: BTreeSet<PathBuf> = BTreeSet::new();
+    dirs.insert(workdir.to_path_buf());
+
+    for entry in index.iter() {
+        let rel = String::from_utf8_lossy(&entry.path);
+        let path = workdir.join(rel.as_ref());
+        rel.hash(&mut hasher);
+        match std::fs::symlink_metadata(&path) {
+            Ok(meta) => {
+                meta.len().hash(&mut hasher);
+                mtime_ns(&path).hash(&mut hasher);
+            }
+            Err(_) => None::<u64>.hash(&mut hasher),
+        }
+
+        let mut parent = path.parent();
+        while let Some(dir) = parent.filter(|d| *d != workdir) {
+            if !dirs.insert(dir.to_path_buf()) {
+                break;
+            }
+            parent = dir.parent();
+        }
+    }
+
+    for dir in &dirs {
+        mtime_ns(dir).hash(&mut hasher);
+    }
+    Some(hasher.finish())
+}
+
+/// Count changed files. A file can be both staged and modified.
+fn count_changes(repo: &Repository) -> Result<ChangeCounts> {
+    let mut counts = ChangeCounts::default();
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(true);
+    for entry in repo.statuses(Some(&mut opts))?.iter() {
+        let s = entry.status();
+        if s.is_conflicted() {
+            counts.conflicted += 1;
+            continue;
+        }
+        if s.intersects(
+            Status::INDEX_NEW
+                | Status::INDEX_MODIFIED
+                | Status::INDEX_DELETED
+                | Status::INDEX_RENAMED
+                | Status::INDEX_TYPECHANGE,
+        ) {
+            counts.staged += 1;
+        }
+        if s.intersects(
+            Status::WT_MODIFIED | Status::WT_DELETED | Status::WT_RENAMED | Status::WT_TYPECHANGE,
+        ) {
+            counts.modified += 1;
+        }
+        if s.contains(Status::WT_NEW) {
+            counts.untracked += 1;
+        }
+    }
+    Ok(counts)
+}
+
+fn operation_in_progress(state: RepositoryState) -> Option<String> {
+    let op = match state {
+        RepositoryState::Clean => return None,
+        RepositoryState::Merge => "merge",
+        RepositoryState::Revert | RepositoryState::RevertSequence => "revert",
+        RepositoryState::CherryPick | RepositoryState::CherryPickSequence => "cherry-pick",
+        RepositoryState::Bisect => "bisect",
+        RepositoryState::Rebase
+        | RepositoryState::RebaseInteractive
+        | RepositoryState::RebaseMerge => "rebase",
+        RepositoryState::ApplyMailbox | RepositoryState::ApplyMailboxOrRebase => "am",
+    };
+    Some(op.to_string())
+}
+
 fn get_sync_status(repo: &Repository) -> SyncStatus {
     if repo.head().is_err() {
         return SyncStatus::NoHead;
@@ -148,3 +656,64 @@ fn get_sync_status(repo: &Repository) -> SyncStatus {
         },
     }
 }
+
+/// Print repositories as a table, with a note line for anything unusual
+pub fn print_repo_table(repos: &[RepoStatus]) {
+    println!(
+        "\n{:<22} {:<16} {:<14} {:<10} {:>5}  Last commit",
+        "Project", "Branch", "Changes", "Sync", "Stash"
+    );
+    println!("{}", "-".repeat(100));
+
+    for repo in repos {
+        let last_commit = repo
+            .last_commit
+            .as_ref()
+            .map(|c| {
+                format!(
+                    "{:>4}  {} ({})",
+                    output::format_age(c.time),
+                    output::truncate(&c.summary, 40),
+                    c.author
+                )
+            })
+            .unwrap_or_else(|| "-".to_string());
+        let name = match repo.kind {
+            RepoKind::Submodule => format!("  ↳ {}", repo.name),
+            _ => repo.name.clone(),
+        };
+        println!(
+            "{:<22} {:<16} {:<14} {:<10} {:>5}  {}",
+            output::truncate(&name, 22),
+            output::truncate(repo.branch.as_deref().unwrap_or("(detached)"), 16),
+            repo.changes_summary(),
+            repo.sync_status.display(),
+            repo.stashes,
+            last_commit
+        );
+
+        let mut notes = Vec::new();
+        match repo.kind {
+            RepoKind::Bare => notes.push("bare repository".to_string()),
+            RepoKind::Worktree => notes.push("linked worktree".to_string()),
+            _ => {}
+        }
+        if let Some(op) = &repo.operation {
+            notes.push(format!("⚠ {} in progress", op));
+        }
+        if repo.branches_without_upstream > 0 {
+            notes.push(format!(
+                "{} local branch{} without upstream",
+                repo.branches_without_upstream,
+                if repo.branches_without_upstream == 1 {
+                    ""
+                } else {
+                    "es"
+                }
+            ));
+        }
+        if !notes.is_empty() {
+            println!("{:<22} └ {}", "", notes.join(", "));
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::config::Config;
+use crate::engine::project;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fs::OpenOptions;
@@ -8,6 +9,12 @@ use std::path::{Path, PathBuf};
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum OpType {
     Move,
+    /// `src` was packed into the archive stub directory `dest`
+    Compress,
+    /// The archive stub `src` was unpacked into `dest`
+    Extract,
+    /// The empty directory `src` was removed (`dest` is unused)
+    RemoveDir,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -41,9 +48,13 @@ fn get_log_path(config: &Config) -> PathBuf {
 }
 
 pub fn log_move(config: &Config, src: &Path, dest: &Path) -> Result<()> {
+    log_op(config, OpType::Move, src, dest)
+}
+
+pub fn log_op(config: &Config, kind: OpType, src: &Path, dest: &Path) -> Result<()> {
     let op = Operation {
         timestamp: chrono::Utc::now().timestamp(),
-        kind: OpType::Move,
+        kind,
         src: src.to_path_buf(),
         dest: dest.to_path_buf(),
     };
@@ -72,7 +83,7 @@ pub fn undo_last(config: &Config, count: usize) -> Result<UndoReport> {
 
     let file = std::fs::File::open(&log_path)?;
     let reader = BufReader::new(file);
-    let lines: Vec<String> = reader.lines().filter_map(|l| l.ok()).collect();
+    let lines: Vec<String> = reader.lines().map_while(Result::ok).collect();
 
     if lines.is_empty() {
         return Ok(UndoReport {
@@ -123,6 +134,28 @@ pub fn undo_last(config: &Config, count: usize) -> Result<UndoReport> {
                     });
                 }
             }
+            OpType::RemoveDir => {
+                let result = std::fs::create_dir_all(&op.src);
+                undone.push(UndoItem {
+                    source: op.dest.clone(),
+                    destination: op.src.clone(),
+                    success: result.is_ok(),
+                    error: result.err().map(|e| e.to_string()),
+                });
+            }
+            OpType::Compress | OpType::Extract => {
+                let result = if matches!(op.kind, OpType::Compress) {
+                    project::extract_project(&op.dest, &op.src)
+                } else {
+                    project::compress_project(&op.dest, &op.src)
+                };
+                undone.push(UndoItem {
+                    source: op.dest.clone(),
+                    destination: op.src.clone(),
+                    success: result.is_ok(),
+                    error: result.err().map(|e| e.to_string()),
+                });
+            }
         }
     }
 
@@ -1,11 +1,21 @@
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{Args, CommandFactory, Parser, Subcommand};
 use log::{error, info, warn};
+use std::io::{IsTerminal, Write};
 use std::path::PathBuf;
-use wardex::config::Config;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use wardex::config::{Config, FOLDER_KEYS};
+use wardex::core::history::JumpHistory;
 use wardex::core::watcher;
-use wardex::engine::{auditor, cleaner, ctf, scaffold, search, stats, status, undo};
+use wardex::engine::project::{self, ProjectFilter, ProjectManifest};
+use wardex::engine::status::StatusSort;
+use wardex::engine::{
+    auditor, cleaner, ctf, git, index, info, reclaim, scaffold, search, stale, stats, status, undo,
+};
 use wardex::tui;
+use wardex::utils::output::{self, ColorChoice, OutputFormat};
+use wardex::utils::shell::{self, CompletionKind, ShellKind};
 
 #[derive(Parser)]
 #[command(name = "wardex")]
@@ -46,6 +56,12 @@ enum CtfCommands {
         name: String,
         #[arg(long, help = "YYYY-MM-DD")]
         date: Option<String>,
+        #[arg(
+            long = "flag-format",
+            value_name = "PREFIX",
+            help = "Flag prefix used by the event, e.g. HTB or picoCTF (repeatable)"
+        )]
+        flag_formats: Vec<String>,
     },
     /// List CTF events
     List,
@@ -64,6 +80,8 @@ enum CtfCommands {
     Solve {
         /// The flag value
         flag: String,
+        #[arg(long, help = "Save the flag even if it doesn't match the event format")]
+        force: bool,
     },
     /// Add a new challenge to current event
     Add {
@@ -93,6 +111,196 @@ enum CtfCommands {
     },
 }
 
+#[derive(Subcommand)]
+enum ProjectCommands {
+    /// Add tags to a project (or remove them with --remove)
+    Tag {
+        #[arg(required = true)]
+        tags: Vec<String>,
+        #[arg(long, help = "Remove the tags instead of adding them")]
+        remove: bool,
+        #[arg(long, default_value = ".", help = "Any path inside the project")]
+        path: PathBuf,
+    },
+    /// Set a manifest field: description, status (active|paused|done), language or link.<name>
+    Set {
+        key: String,
+        #[arg(help = "New value (empty string clears the field)")]
+        value: String,
+        #[arg(long, default_value = ".", help = "Any path inside the project")]
+        path: PathBuf,
+    },
+    /// Move a project into archives
+    Archive {
+        name: String,
+        #[arg(long, help = "Pack into a .tar.zst, leaving a stub manifest")]
+        compress: bool,
+        #[arg(long, help = "Archive even with uncommitted or unpushed work")]
+        force: bool,
+    },
+    /// Bring an archived project back into projects
+    Restore { name: String },
+    /// Move a project between projects, areas and resources
+    Move {
+        name: String,
+        #[arg(long, help = "Destination: projects, areas or resources")]
+        to: String,
+    },
+    /// Show the manifest of a project
+    Show {
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        #[arg(long, default_value = "human", value_parser = output::document_format())]
+        format: OutputFormat,
+    },
+}
+
+#[derive(Subcommand)]
+enum GitCommands {
+    /// Fetch all remotes
+    Fetch {
+        #[command(flatten)]
+        repos: RepoFilterArgs,
+    },
+    /// Pull the current branch
+    Pull {
+        #[arg(long, help = "Refuse to merge; only fast-forward")]
+        ff_only: bool,
+        #[command(flatten)]
+        repos: RepoFilterArgs,
+    },
+    /// Push the current branch
+    Push {
+        #[command(flatten)]
+        repos: RepoFilterArgs,
+    },
+}
+
+#[derive(Subcommand)]
+enum StatsCommands {
+    /// du-style size tree with the largest directories and files
+    Tree {
+        /// Directory to break down (default: the workspace)
+        path: Option<PathBuf>,
+        #[arg(short, long, default_value_t = 2, help = "Directory levels shown")]
+        depth: usize,
+        #[arg(
+            long,
+            default_value_t = 10,
+            help = "Largest subdirectories per level, and files"
+        )]
+        top: usize,
+        #[arg(
+            long,
+            help = "Also count files hidden by .gitignore (target/, node_modules/, ...)"
+        )]
+        include_ignored: bool,
+        #[arg(
+            long,
+            value_enum,
+            default_value_t = OutputFormat::Human,
+            help = "Output format; ndjson and csv list only the largest files"
+        )]
+        format: OutputFormat,
+    },
+    /// Show growth over time from snapshots taken with `stats --record`
+    History {
+        #[arg(
+            long,
+            value_name = "NAME",
+            help = "Show the size history of one project"
+        )]
+        project: Option<String>,
+        #[arg(
+            short = 'n',
+            long,
+            default_value_t = 10,
+            help = "Most recent snapshots shown"
+        )]
+        limit: usize,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Human)]
+        format: OutputFormat,
+    },
+}
+
+/// Repository selection shared by the bulk git commands
+#[derive(Args)]
+struct RepoFilterArgs {
+    #[arg(long, help = "Only repos with uncommitted changes")]
+    dirty: bool,
+    #[arg(long, help = "Only repos behind their upstream (as of the last fetch)")]
+    behind: bool,
+    #[arg(
+        long,
+        value_name = "GLOB",
+        help = "Only repos whose workspace-relative path or name matches"
+    )]
+    path: Option<String>,
+    #[arg(
+        short,
+        long,
+        default_value_t = 8,
+        help = "Repositories processed at once"
+    )]
+    jobs: usize,
+}
+
+impl RepoFilterArgs {
+    fn to_filter(&self) -> git::RepoFilter {
+        git::RepoFilter {
+            dirty: self.dirty,
+            behind: self.behind,
+            path: self.path.clone(),
+        }
+    }
+}
+
+/// Filters on project manifest fields, shared by find and stats
+#[derive(Args)]
+struct ProjectFilterArgs {
+    #[arg(
+        long = "tag",
+        value_name = "TAG",
+        help = "Only projects with this tag (repeatable)"
+    )]
+    tags: Vec<String>,
+    #[arg(long, help = "Only projects with this status (active|paused|done)")]
+    status: Option<String>,
+    #[arg(
+        long = "lang",
+        value_name = "LANGUAGE",
+        help = "Only projects in this language"
+    )]
+    language: Option<String>,
+}
+
+impl ProjectFilterArgs {
+    fn to_filter(&self) -> Result<ProjectFilter> {
+        Ok(ProjectFilter {
+            tags: self.tags.clone(),
+            status: self.status.as_deref().map(str::parse).transpose()?,
+            language: self.language.clone(),
+        })
+    }
+}
+
+#[derive(Subcommand)]
+enum ShellCommands {
+    /// Print shell integration (wx wrapper + completions) to eval in your rc file
+    Init {
+        #[arg(value_enum)]
+        shell: ShellKind,
+    },
+    /// Print dynamic completion candidates (used by the generated scripts)
+    #[command(hide = true)]
+    Complete {
+        #[arg(value_enum)]
+        kind: CompletionKind,
+        /// Event to list challenges of
+        event: Option<String>,
+    },
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Initialize a new project
@@ -113,7 +321,25 @@ enum Commands {
         command: CtfCommands,
     },
     /// Audit workspace health (files, empty folders)
-    Audit,
+    Audit {
+        #[arg(
+            long = "check",
+            value_name = "ID",
+            value_delimiter = ',',
+            help = "Only run these checks (default: audit.enabled, or all)"
+        )]
+        checks: Vec<String>,
+        #[arg(long, help = "List the available checks and exit")]
+        list_checks: bool,
+        #[arg(long, help = "Fix what can be fixed (journaled for `wardex undo`)")]
+        fix: bool,
+        #[arg(long, requires = "fix", help = "Show the fixes without applying them")]
+        dry_run: bool,
+        #[arg(short, long, requires = "fix", help = "Skip the confirmation prompt")]
+        yes: bool,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Human)]
+        format: OutputFormat,
+    },
     /// Undo last movement operation
     Undo {
         #[arg(short, long, default_value_t = 1)]
@@ -122,29 +348,439 @@ enum Commands {
     /// Watch Inbox and auto-sort
     Watch,
     /// Show git status dashboard
-    Status,
+    Status {
+        #[arg(
+            long,
+            help = "Only repos with staged, modified, untracked or conflicted files"
+        )]
+        dirty: bool,
+        #[arg(
+            long,
+            help = "Only repos with commits ahead of upstream or local branches without one"
+        )]
+        unpushed: bool,
+        #[arg(long, value_enum, default_value_t = StatusSort::Name)]
+        sort: StatusSort,
+        #[arg(
+            long,
+            help = "Discovery depth below the workspace (default: status.discovery_depth)"
+        )]
+        depth: Option<usize>,
+        #[arg(long, help = "Also list submodules under their parent repository")]
+        submodules: bool,
+        #[arg(long, help = "Ignore the status cache and rescan every repository")]
+        refresh: bool,
+        #[arg(
+            long,
+            help = "Rescan every repo and exit non-zero if any is dirty, unpushed or diverged, listing why"
+        )]
+        check: bool,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Human)]
+        format: OutputFormat,
+    },
+    /// Rank projects by time since their last commit or file change
+    Stale {
+        #[arg(
+            long,
+            default_value_t = 90,
+            help = "Idle days before a project counts as stale"
+        )]
+        days: i64,
+        #[arg(long, help = "List every project, not just the stale ones")]
+        all: bool,
+        #[arg(long, help = "Archive the stale projects that have no unsaved work")]
+        archive: bool,
+        #[arg(
+            long,
+            requires = "archive",
+            help = "Compress archived projects to .tar.zst"
+        )]
+        compress: bool,
+        #[arg(
+            short,
+            long,
+            requires = "archive",
+            help = "Skip the confirmation prompt"
+        )]
+        yes: bool,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Human)]
+        format: OutputFormat,
+    },
+    /// Find build artifacts and dependency caches (target/, node_modules/, .venv/, ...)
+    Reclaim {
+        #[arg(
+            long,
+            default_value_t = 30,
+            help = "Idle days before a project's artifacts may be cleaned"
+        )]
+        days: i64,
+        #[arg(long, help = "Clean the artifacts of projects idle for --days")]
+        clean: bool,
+        #[arg(
+            long,
+            requires = "clean",
+            help = "Delete permanently instead of moving to the trash"
+        )]
+        delete: bool,
+        #[arg(long, requires = "clean", help = "Show what would be cleaned")]
+        dry_run: bool,
+        #[arg(short, long, requires = "clean", help = "Skip the confirmation prompt")]
+        yes: bool,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Human)]
+        format: OutputFormat,
+    },
     /// Search for flags recursively
     Search {
         #[arg(default_value = ".")]
         path: PathBuf,
         #[arg(short, long)]
         pattern: Option<String>,
+        #[arg(
+            long,
+            help = "Extract printable ASCII/UTF-16 strings from binary files and search those"
+        )]
+        strings: bool,
+        #[arg(
+            long,
+            default_value_t = 4,
+            help = "Minimum string length in --strings mode"
+        )]
+        min_len: usize,
+        #[arg(
+            long,
+            value_enum,
+            default_value_t = OutputFormat::Human,
+            help = "Output format; ndjson ends with a {\"summary\": ...} line of skipped files and errors, csv lists only matches"
+        )]
+        format: OutputFormat,
+    },
+    /// Fuzzy find projects (prints the best match when piped, e.g. `cd $(wardex find api)`)
+    Find {
+        #[arg(default_value = "")]
+        name: String,
+        #[arg(
+            short,
+            long,
+            help = "Pick interactively, narrowing results as you type"
+        )]
+        pick: bool,
+        #[command(flatten)]
+        filter: ProjectFilterArgs,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Human)]
+        format: OutputFormat,
     },
-    /// Fuzzy find projects
-    Find { name: String },
     /// Grep Content in Projects/Resources
-    Grep { pattern: String },
+    Grep {
+        pattern: String,
+        #[arg(
+            long = "in",
+            value_name = "ROOT",
+            help = "Where to search: workspace|inbox|projects|areas|resources|archives|ctf or a paths.custom key (repeatable, default: projects + resources)"
+        )]
+        roots: Vec<String>,
+        #[arg(
+            short = 't',
+            long = "type",
+            value_name = "TYPE",
+            help = "Only search files of this type (rust, py, md, ...)"
+        )]
+        types: Vec<String>,
+        #[arg(
+            short = 'g',
+            long = "glob",
+            value_name = "GLOB",
+            help = "Include files matching GLOB, or exclude with !GLOB"
+        )]
+        globs: Vec<String>,
+        #[arg(short = 'i', long)]
+        ignore_case: bool,
+        #[arg(short = 'w', long, help = "Only match whole words")]
+        word_regexp: bool,
+        #[arg(short = 'F', long, help = "Treat the pattern as a literal string")]
+        fixed_strings: bool,
+        #[arg(
+            short = 'A',
+            long,
+            value_name = "NUM",
+            help = "Lines of context after each match"
+        )]
+        after_context: Option<usize>,
+        #[arg(
+            short = 'B',
+            long,
+            value_name = "NUM",
+            help = "Lines of context before each match"
+        )]
+        before_context: Option<usize>,
+        #[arg(
+            short = 'C',
+            long,
+            value_name = "NUM",
+            help = "Lines of context around each match"
+        )]
+        context: Option<usize>,
+        #[arg(
+            short = 'm',
+            long,
+            value_name = "NUM",
+            help = "Stop after NUM matching lines per file"
+        )]
+        max_count: Option<u64>,
+        #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+        color: ColorChoice,
+        #[arg(long, help = "Ignore the content index and scan every file")]
+        no_index: bool,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Human)]
+        format: OutputFormat,
+    },
+    /// Build or refresh the content index used by grep
+    Index {
+        #[arg(long, help = "Discard the existing index and re-read every file")]
+        rebuild: bool,
+    },
     /// Show workspace analytics
-    Stats,
+    Stats {
+        #[command(subcommand)]
+        command: Option<StatsCommands>,
+        #[arg(long, help = "Append a snapshot to the workspace stats history")]
+        record: bool,
+        #[arg(
+            long,
+            help = "Also count files hidden by .gitignore (target/, node_modules/, ...)"
+        )]
+        include_ignored: bool,
+        #[arg(long, default_value_t = 10, help = "Rows shown per breakdown")]
+        top: usize,
+        #[command(flatten)]
+        filter: ProjectFilterArgs,
+    },
+    /// Run git fetch, pull or push across workspace repositories in parallel
+    Git {
+        #[command(subcommand)]
+        command: GitCommands,
+    },
+    /// Manage project metadata (.wardex.toml)
+    Project {
+        #[command(subcommand)]
+        command: ProjectCommands,
+    },
     /// Launch interactive TUI dashboard
     Dashboard,
-    /// Quick file/project info
-    Info { path: Option<PathBuf> },
+    /// Inspect a file or directory: size, languages, git, PARA and CTF context
+    Info {
+        path: Option<PathBuf>,
+        #[arg(long, default_value = "human", value_parser = output::document_format())]
+        format: OutputFormat,
+    },
     /// Manage configuration
     Config {
         #[command(subcommand)]
         command: ConfigCommands,
     },
+    /// Shell integration and completions
+    Shell {
+        #[command(subcommand)]
+        command: ShellCommands,
+    },
+}
+
+/// Clean the artifacts of projects idle for `days`, after confirmation
+fn clean_artifacts(
+    report: &reclaim::ReclaimReport,
+    days: i64,
+    delete: bool,
+    dry_run: bool,
+    yes: bool,
+) -> Result<()> {
+    let candidates: Vec<&reclaim::Artifact> = report.reclaimable(days).collect();
+    if candidates.is_empty() {
+        info!("No artifacts in projects idle for {}+ days.", days);
+        return Ok(());
+    }
+    let total = output::human_size(candidates.iter().map(|a| a.size_bytes).sum());
+    let action = if delete { "Delete" } else { "Trash" };
+
+    if !yes && !dry_run {
+        let confirmed = dialoguer::Confirm::new()
+            .with_prompt(format!(
+                "{} {} artifact dirs ({})?",
+                action,
+                candidates.iter().map(|a| a.paths.len()).sum::<usize>(),
+                total
+            ))
+            .default(false)
+            .interact()?;
+        if !confirmed {
+            info!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    let cleaned = reclaim::clean_artifacts(&candidates, delete, dry_run);
+    let mut failed = 0;
+    for c in &cleaned {
+        match (&c.error, dry_run) {
+            (Some(e), _) => {
+                failed += 1;
+                error!("✗ {}: {}", c.path.display(), e);
+            }
+            (None, true) => info!(
+                "[DRY RUN] Would {} {}",
+                action.to_lowercase(),
+                c.path.display()
+            ),
+            (None, false) => info!(
+                "✓ {} {}",
+                if delete { "Deleted" } else { "Trashed" },
+                c.path.display()
+            ),
+        }
+    }
+    if failed > 0 {
+        anyhow::bail!(
+            "{} of {} artifact dirs could not be cleaned",
+            failed,
+            cleaned.len()
+        );
+    }
+    if !dry_run {
+        info!("Reclaimed {}.", total);
+    }
+    Ok(())
+}
+
+/// Apply the fixes for `report`'s findings, after confirmation
+fn fix_audit_findings(
+    config: &Config,
+    report: &auditor::AuditReport,
+    dry_run: bool,
+    yes: bool,
+) -> Result<()> {
+    let fixes = auditor::plan_fixes(config, report);
+    if fixes.is_empty() {
+        info!("Nothing to fix.");
+        return Ok(());
+    }
+
+    if !yes && !dry_run {
+        for fix in &fixes {
+            println!(" - {}", fix);
+        }
+        let confirmed = dialoguer::Confirm::new()
+            .with_prompt(format!("Apply {} fixes?", fixes.len()))
+            .default(false)
+            .interact()?;
+        if !confirmed {
+            info!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    let outcomes = auditor::apply_fixes(config, &fixes, dry_run);
+    let mut failed = 0;
+    for outcome in &outcomes {
+        match (&outcome.error, dry_run) {
+            (Some(e), _) => {
+                failed += 1;
+                error!("✗ {}: {}", outcome.fix, e);
+            }
+            (None, true) => info!("[DRY RUN] Would {}", outcome.fix),
+            (None, false) => info!("✓ Fixed: {}", outcome.fix),
+        }
+    }
+
+    let applied = outcomes.len() - failed;
+    if !dry_run && applied > 0 {
+        info!(
+            "Applied {} fixes. Revert with `wardex undo -c {}`.",
+            applied, applied
+        );
+    }
+    if failed > 0 {
+        anyhow::bail!("{} of {} fixes failed", failed, outcomes.len());
+    }
+    Ok(())
+}
+
+/// Archive the stale projects that are safe to archive, after confirmation
+fn archive_stale_projects(
+    config: &Config,
+    report: &stale::StaleReport,
+    days: i64,
+    compress: bool,
+    yes: bool,
+) -> Result<()> {
+    let candidates: Vec<&stale::StaleProject> =
+        report.stale(days).filter(|p| p.is_clean()).collect();
+    let skipped = report.stale(days).count() - candidates.len();
+    if skipped > 0 {
+        warn!("Skipping {} stale projects with unsaved work.", skipped);
+    }
+    if candidates.is_empty() {
+        info!("Nothing to archive.");
+        return Ok(());
+    }
+
+    let names: Vec<&str> = candidates.iter().map(|p| p.name.as_str()).collect();
+    if !yes {
+        let confirmed = dialoguer::Confirm::new()
+            .with_prompt(format!(
+                "Archive {} projects ({})?",
+                names.len(),
+                names.join(", ")
+            ))
+            .default(false)
+            .interact()?;
+        if !confirmed {
+            info!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    let mut archived = 0;
+    for name in names {
+        match project::archive_project(config, name, compress, false) {
+            Ok(moved) => {
+                archived += 1;
+                info!("✓ Archived {} -> {:?}", name, moved.destination);
+            }
+            Err(e) => error!("✗ {}: {}", name, e),
+        }
+    }
+    info!(
+        "Archived {} projects. Revert with `wardex undo -c {}`.",
+        archived, archived
+    );
+    Ok(())
+}
+
+fn handle_shell_command(command: &ShellCommands, config_path: Option<&PathBuf>) -> Result<()> {
+    match command {
+        ShellCommands::Init { shell } => {
+            let mut completions = Vec::new();
+            clap_complete::generate(
+                shell.clap_shell(),
+                &mut Cli::command(),
+                "wardex",
+                &mut completions,
+            );
+            print!(
+                "{}",
+                shell::init_script(*shell, &String::from_utf8_lossy(&completions))
+            );
+        }
+        ShellCommands::Complete { kind, event } => {
+            // Completion must never print errors into the user's prompt
+            let config =
+                find_config(&config_path.cloned()).and_then(|path| Config::load_from_file(&path));
+            if let Ok(config) = config {
+                for value in shell::complete(&config, *kind, event.as_deref()).unwrap_or_default() {
+                    println!("{}", value);
+                }
+            }
+        }
+    }
+    Ok(())
 }
 
 /// Search for config file in priority order
@@ -198,6 +834,10 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    if let Commands::Shell { command } = &cli.command {
+        return handle_shell_command(command, cli.config.as_ref());
+    }
+
     let config_path = find_config(&cli.config)?;
     let config = Config::load_from_file(&config_path)?;
 
@@ -250,8 +890,12 @@ fn main() -> Result<()> {
             );
         }
         Commands::Ctf { command } => match command {
-            CtfCommands::Init { name, date } => {
-                let result = ctf::create_event(&config, name, date.clone())?;
+            CtfCommands::Init {
+                name,
+                date,
+                flag_formats,
+            } => {
+                let result = ctf::create_event(&config, name, date.clone(), flag_formats)?;
 
                 if result.already_exists {
                     error!("Event directory already exists: {:?}", result.event_dir);
@@ -276,17 +920,22 @@ fn main() -> Result<()> {
                 }
 
                 println!(
-                    "{:<30} {:<6} {:<12} {:<10}",
-                    "Event", "Year", "Date", "Challenges"
+                    "{:<30} {:<6} {:<12} {:<10} {:<6}",
+                    "Event", "Year", "Date", "Challenges", "Solved"
                 );
-                println!("{}", "-".repeat(60));
+                println!("{}", "-".repeat(68));
 
                 for event in &result.events {
                     let date_str = event.date.as_deref().unwrap_or("-");
                     let meta_indicator = if event.has_metadata { "" } else { "*" };
                     println!(
-                        "{:<30} {:<6} {:<12} {:<10}{}",
-                        event.name, event.year, date_str, event.challenge_count, meta_indicator
+                        "{:<30} {:<6} {:<12} {:<10} {:<6}{}",
+                        event.name,
+                        event.year,
+                        date_str,
+                        event.challenge_count,
+                        event.solved_count,
+                        meta_indicator
                     );
                 }
 
@@ -297,8 +946,8 @@ fn main() -> Result<()> {
             CtfCommands::Import { file, category } => {
                 ctf::import_challenge(&config, file, category.clone())?;
             }
-            CtfCommands::Solve { flag } => {
-                ctf::solve_challenge(&config, &flag)?;
+            CtfCommands::Solve { flag, force } => {
+                ctf::solve_challenge(&config, flag, *force)?;
             }
             CtfCommands::Add { path } => {
                 ctf::add_challenge(&config, path)?;
@@ -320,9 +969,28 @@ fn main() -> Result<()> {
                 ctf::set_active_event(&config, event)?;
             }
         },
-        Commands::Audit => {
+        Commands::Audit {
+            checks,
+            list_checks,
+            fix,
+            dry_run,
+            yes,
+            format,
+        } => {
+            if *list_checks {
+                for check in auditor::all_checks() {
+                    println!(
+                        "{:<16} {:<8} {}",
+                        check.id(),
+                        check.severity().to_string(),
+                        check.description()
+                    );
+                }
+                return Ok(());
+            }
+
             info!("Auditing workspace...");
-            let report = auditor::audit_workspace(&config)?;
+            let report = auditor::audit_workspace(&config, checks)?;
 
             if report.workspace_not_found {
                 error!(
@@ -334,27 +1002,15 @@ fn main() -> Result<()> {
 
             info!("Analyzed {} items.", report.items_scanned);
 
-            if !report.empty_folders.is_empty() {
-                warn!("Empty Folders Found: {}", report.empty_folders.len());
-                for p in report.empty_folders.iter().take(10) {
-                    println!(" - {:?}", p);
-                }
-                if report.empty_folders.len() > 10 {
-                    println!("... and {} more", report.empty_folders.len() - 10);
-                }
+            if !format.is_human() {
+                output::print_records(*format, &report.findings)?;
+            } else {
+                auditor::print_findings(&report);
             }
 
-            if !report.suspicious_extensions.is_empty() {
-                warn!("Suspicious Extensions (Magic Byte Mismatch):");
-                for item in &report.suspicious_extensions {
-                    println!(
-                        " - {:?} (Named: .{}, Real: .{})",
-                        item.path, item.declared_ext, item.actual_ext
-                    );
-                }
+            if *fix {
+                fix_audit_findings(&config, &report, *dry_run, *yes)?;
             }
-
-            info!("✓ Audit Complete.");
         }
         Commands::Undo { count } => {
             let report = undo::undo_last(&config, *count)?;
@@ -397,61 +1053,235 @@ fn main() -> Result<()> {
         Commands::Watch => {
             watcher::watch_inbox(&config)?;
         }
-        Commands::Status => {
+        Commands::Status {
+            dirty,
+            unpushed,
+            sort,
+            depth,
+            submodules,
+            refresh,
+            check,
+            format,
+        } => {
             info!("Scanning workspace: {:?}", config.resolve_path("workspace"));
-            let report = status::show_status(&config)?;
+            let mut config = config.clone();
+            if let Some(depth) = depth {
+                config.status.discovery_depth = *depth;
+            }
+            config.status.submodules |= *submodules;
+            // A check gates backups and the like, so never trust the cache
+            let mut report = status::show_status(&config, *refresh || *check)?;
 
             if report.workspace_not_found {
-                error!("Workspace not found.");
-                return Ok(());
+                anyhow::bail!(
+                    "Workspace not found: {:?}",
+                    config.resolve_path("workspace")
+                );
             }
 
             if report.repos.is_empty() {
                 warn!("No git repositories found.");
-                return Ok(());
             }
 
-            println!(
-                "\n{:<25} {:<12} {:<15} {}",
-                "Project", "State", "Sync", "Path"
-            );
-            println!("{}", "-".repeat(80));
+            let total = report.repos.len();
+            let dirty_count = report.repos.iter().filter(|r| r.is_dirty).count();
+            let cached_count = report.repos.iter().filter(|r| r.from_cache).count();
+            report
+                .repos
+                .retain(|r| (!dirty || r.is_dirty) && (!unpushed || r.has_local_only_work()));
+            status::sort_repos(&mut report.repos, *sort);
 
-            for repo in &report.repos {
-                let state = if repo.is_dirty {
-                    "⚠ Dirty"
-                } else {
-                    "✓ Clean"
-                };
-                println!(
-                    "{:<25} {:<12} {:<15} {}",
-                    repo.name,
-                    state,
-                    repo.sync_status.display(),
-                    repo.path.display()
+            match format {
+                OutputFormat::Json => output::print_json(&report)?,
+                OutputFormat::Ndjson => output::print_records(*format, &report.repos)?,
+                OutputFormat::Csv => {
+                    let rows: Vec<status::RepoRow> = report
+                        .repos
+                        .iter()
+                        .map(status::RepoStatus::to_row)
+                        .collect();
+                    output::print_records(*format, &rows)?;
+                }
+                OutputFormat::Human => {}
+            }
+            if format.is_human() && !check && !report.repos.is_empty() {
+                status::print_repo_table(&report.repos);
+            }
+
+            if *check {
+                let failing: Vec<&status::RepoStatus> = report
+                    .repos
+                    .iter()
+                    .filter(|r| !r.is_fully_saved())
+                    .collect();
+                if format.is_human() {
+                    for repo in &failing {
+                        println!("{}: {}", repo.path.display(), repo.problems.join(", "));
+                    }
+                }
+                if !failing.is_empty() {
+                    anyhow::bail!(
+                        "{} of {} repos need attention",
+                        failing.len(),
+                        report.repos.len()
+                    );
+                }
+                info!(
+                    "✓ All {} repos are committed and pushed",
+                    report.repos.len()
+                );
+            } else if total > 0 {
+                info!(
+                    "Total: {} repos ({} dirty, {} shown, {} from cache)",
+                    total,
+                    dirty_count,
+                    report.repos.len(),
+                    cached_count
                 );
             }
+        }
+        Commands::Stale {
+            days,
+            all,
+            archive,
+            compress,
+            yes,
+            format,
+        } => {
+            let report = stale::find_stale(&config)?;
+            if report.projects_dir_missing {
+                error!("Projects folder not found.");
+                return Ok(());
+            }
 
-            let dirty_count = report.repos.iter().filter(|r| r.is_dirty).count();
-            info!(
-                "Total: {} repos ({} dirty)",
-                report.repos.len(),
-                dirty_count
-            );
+            let listed: Vec<&stale::StaleProject> = if *all {
+                report.projects.iter().collect()
+            } else {
+                report.stale(*days).collect()
+            };
+
+            if !format.is_human() {
+                output::print_records(*format, &listed)?;
+            } else if listed.is_empty() {
+                info!("No projects idle for {}+ days.", days);
+            } else {
+                stale::print_stale_projects(&listed);
+            }
+
+            if *archive {
+                archive_stale_projects(&config, &report, *days, *compress, *yes)?;
+            }
         }
-        Commands::Search { path, pattern } => {
+        Commands::Reclaim {
+            days,
+            clean,
+            delete,
+            dry_run,
+            yes,
+            format,
+        } => {
+            let report = reclaim::find_artifacts(&config)?;
+            if report.workspace_not_found {
+                anyhow::bail!("Workspace not found.");
+            }
+
+            match format {
+                OutputFormat::Json => output::print_json(&report.artifacts)?,
+                OutputFormat::Ndjson => output::print_records(*format, &report.artifacts)?,
+                OutputFormat::Csv => {
+                    let rows: Vec<reclaim::ArtifactRow> = report
+                        .artifacts
+                        .iter()
+                        .map(reclaim::Artifact::to_row)
+                        .collect();
+                    output::print_records(*format, &rows)?;
+                }
+                OutputFormat::Human if report.artifacts.is_empty() => {
+                    info!("No build artifacts or dependency caches found.");
+                }
+                OutputFormat::Human => {
+                    reclaim::print_artifacts(&report.artifacts, *days);
+                    let reclaimable: u64 = report.reclaimable(*days).map(|a| a.size_bytes).sum();
+                    info!(
+                        "Total: {} in {} artifact dirs, {} in projects idle {}+ days",
+                        output::human_size(report.total_bytes()),
+                        report.artifacts.len(),
+                        output::human_size(reclaimable),
+                        days
+                    );
+                }
+            }
+
+            if *clean {
+                clean_artifacts(&report, *days, *delete, *dry_run, *yes)?;
+            }
+        }
+        Commands::Search {
+            path,
+            pattern,
+            strings,
+            min_len,
+            format,
+        } => {
             info!("Searching for flags in {:?}...", path);
-            let report = search::find_flags(path, pattern.clone())?;
+            let pattern = pattern.clone().or_else(|| {
+                let (event, pattern) = ctf::event_flag_pattern(path)?;
+                info!("Using flag format of event '{}': {}", event, pattern);
+                Some(pattern)
+            });
+            let options = search::FlagSearchOptions {
+                pattern,
+                strings: *strings,
+                min_string_len: *min_len,
+            };
+            let show_progress = std::io::stderr().is_terminal();
+            let progress = search::SearchProgress::default();
+            let done = AtomicBool::new(false);
 
-            for m in &report.matches {
-                let location = if let Some(ref entry) = m.archive_entry {
-                    format!("{} (in {})", entry, m.file_path)
-                } else if let Some(line) = m.line_number {
-                    format!("{}:{}", m.file_path, line)
-                } else {
-                    m.file_path.clone()
-                };
-                println!("✓ {}: {}", location, m.matched_text);
+            let report = std::thread::scope(|s| {
+                if show_progress {
+                    s.spawn(|| render_search_progress(&progress, &done));
+                }
+                let report =
+                    search::find_flags_streaming(path, &options, &config.search, &progress, |m| {
+                        if show_progress {
+                            eprint!("\r\x1b[2K");
+                        }
+                        match format {
+                            OutputFormat::Human => {}
+                            OutputFormat::Ndjson => {
+                                if let Err(e) = output::print_ndjson_line(m) {
+                                    log::debug!("Failed to write match: {}", e);
+                                }
+                                return;
+                            }
+                            _ => return,
+                        }
+                        let location = if let Some(ref entry) = m.archive_entry {
+                            format!("{} (in {})", entry, m.file_path)
+                        } else if let Some(line) = m.line_number {
+                            format!("{}:{}", m.file_path, line)
+                        } else {
+                            m.file_path.clone()
+                        };
+                        let location = match m.byte_offset {
+                            Some(offset) => format!("{} @ {:#x}", location, offset),
+                            None => location,
+                        };
+                        println!("✓ {}: {}", location, m.matched_text);
+                    });
+                done.store(true, Ordering::Relaxed);
+                report
+            })?;
+
+            match format {
+                OutputFormat::Json => output::print_json(&report)?,
+                // Matches were streamed; close with a line holding the rest
+                OutputFormat::Ndjson => {
+                    output::print_ndjson_line(&serde_json::json!({ "summary": report.summary() }))?
+                }
+                OutputFormat::Csv => output::print_csv(&report.matches)?,
+                OutputFormat::Human => {}
             }
 
             info!(
@@ -460,6 +1290,17 @@ fn main() -> Result<()> {
                 report.matches.len()
             );
 
+            if !report.skipped.is_empty() {
+                warn!("{} files/entries skipped:", report.skipped.len());
+                for skip in report.skipped.iter().take(10) {
+                    let location = match &skip.archive_entry {
+                        Some(entry) => format!("{} (in {})", entry, skip.file_path),
+                        None => skip.file_path.clone(),
+                    };
+                    info!("  - {}: {}", location, skip.reason);
+                }
+            }
+
             if !report.errors.is_empty() {
                 warn!("{} errors occurred:", report.errors.len());
                 for e in report.errors.iter().take(5) {
@@ -467,54 +1308,307 @@ fn main() -> Result<()> {
                 }
             }
         }
-        Commands::Find { name } => {
-            let results = search::find_project(&config, name)?;
-            if results.is_empty() {
+        Commands::Find {
+            name,
+            pick,
+            filter,
+            format,
+        } => {
+            let mut history = JumpHistory::load();
+            let filter = filter.to_filter()?;
+
+            if *pick {
+                if !std::io::stderr().is_terminal() {
+                    anyhow::bail!("--pick needs an interactive terminal");
+                }
+                let candidates: Vec<_> = search::project_candidates(&config, &history)
+                    .into_iter()
+                    .filter(|c| filter.matches(c.manifest.as_ref()))
+                    .collect();
+                if let Some(path) = tui::picker::pick(&candidates, name)? {
+                    history.record(&path)?;
+                    println!("{}", path.display());
+                }
+                return Ok(());
+            }
+
+            let results = search::find_project(&config, name, &filter)?;
+            if !format.is_human() {
+                output::print_records(*format, &results)?;
+            } else if !std::io::stdout().is_terminal() {
+                // Piped (e.g. `cd $(wardex find api)`): emit only the best path
+                let Some(best) = results.first() else {
+                    anyhow::bail!("No projects found matching '{}'", name);
+                };
+                history.record(&best.path)?;
+                println!("{}", best.path.display());
+            } else if results.is_empty() {
                 warn!("No projects found matching '{}'", name);
             } else {
-                println!("{:<50} {:<10}", "Project Path", "Score");
-                println!("{}", "-".repeat(60));
+                println!("{:<50} {:<6} {:<8} Tags", "Project Path", "Score", "Status");
+                println!("{}", "-".repeat(80));
                 for res in results.iter().take(10) {
-                    println!("{:<50} {}", res.path.display(), res.score);
+                    println!(
+                        "{:<50} {:<6} {:<8} {}",
+                        res.path.display(),
+                        res.score,
+                        res.status.as_deref().unwrap_or("-"),
+                        res.tags
+                    );
                 }
             }
         }
-        Commands::Grep { pattern } => {
-            info!("Grepping in Projects & Resources...");
-            let matches = search::content_search(&config, pattern)?;
+        Commands::Grep {
+            pattern,
+            roots,
+            types,
+            globs,
+            ignore_case,
+            word_regexp,
+            fixed_strings,
+            after_context,
+            before_context,
+            context,
+            max_count,
+            color,
+            no_index,
+            format,
+        } => {
+            let options = search::GrepOptions {
+                roots: roots.clone(),
+                types: types.clone(),
+                globs: globs.clone(),
+                ignore_case: *ignore_case,
+                word: *word_regexp,
+                fixed_strings: *fixed_strings,
+                before_context: before_context.or(*context).unwrap_or(0),
+                after_context: after_context.or(*context).unwrap_or(0),
+                max_count: *max_count,
+                use_index: !no_index,
+            };
+            if roots.is_empty() {
+                info!("Grepping in Projects & Resources...");
+            } else {
+                info!("Grepping in {}...", roots.join(", "));
+            }
+            let mut matches = search::content_search(&config, pattern, &options)?;
 
-            for m in &matches {
-                println!(
-                    "{}:{}: {}",
-                    m.file_path,
-                    m.line_number.unwrap_or(0),
-                    m.matched_text
-                );
+            match format {
+                OutputFormat::Human => search::print_grep_matches(
+                    &matches,
+                    color.enabled(),
+                    options.before_context + options.after_context > 0,
+                ),
+                OutputFormat::Csv => {
+                    // Context lines don't fit a flat CSV row
+                    for m in &mut matches {
+                        m.context_before.clear();
+                        m.context_after.clear();
+                    }
+                    output::print_csv(&matches)?;
+                }
+                _ => output::print_records(*format, &matches)?,
             }
             info!("Found {} matches.", matches.len());
         }
-        Commands::Stats => {
-            let stats = stats::get_stats(&config)?;
-            stats::print_stats(&stats);
+        Commands::Index { rebuild } => {
+            info!("Indexing Projects & Resources...");
+            let report = index::build_index(&config, *rebuild)?;
+            info!(
+                "Indexed {} files ({} read, {} unchanged, {} too large to index)",
+                report.files, report.files_read, report.files_reused, report.files_unindexed
+            );
+            info!(
+                "{} trigrams, {:.1} KB -> {}",
+                report.trigrams,
+                report.size_bytes as f64 / 1024.0,
+                report.index_path.display()
+            );
+        }
+        Commands::Stats {
+            command:
+                Some(StatsCommands::History {
+                    project,
+                    limit,
+                    format,
+                }),
+            ..
+        } => {
+            let history = stats::load_history(&config)?;
+            let shown = &history[history.len().saturating_sub(*limit)..];
+            if shown.is_empty() {
+                info!("No snapshots yet. Record one with `wardex stats --record`.");
+                return Ok(());
+            }
+
+            if let Some(name) = project {
+                let series = stats::project_series(shown, name);
+                if format.is_human() {
+                    stats::print_project_history(name, &series);
+                } else {
+                    output::print_records(*format, &series)?;
+                }
+                return Ok(());
+            }
+            match format {
+                OutputFormat::Human => stats::print_history(shown),
+                OutputFormat::Json => output::print_json(shown)?,
+                OutputFormat::Ndjson => {
+                    for snapshot in shown {
+                        output::print_ndjson_line(snapshot)?;
+                    }
+                }
+                OutputFormat::Csv => anyhow::bail!("CSV history needs --project"),
+            }
         }
+        Commands::Stats {
+            command:
+                Some(StatsCommands::Tree {
+                    path,
+                    depth,
+                    top,
+                    include_ignored,
+                    format,
+                }),
+            ..
+        } => {
+            let root = path
+                .clone()
+                .unwrap_or_else(|| config.resolve_path("workspace"));
+            let report = stats::size_tree(&root, *depth, *top, *include_ignored)?;
+            match format {
+                OutputFormat::Human => stats::print_size_tree(&report),
+                OutputFormat::Json => output::print_json(&report)?,
+                // The tree doesn't flatten into records; list the largest files
+                OutputFormat::Ndjson | OutputFormat::Csv => {
+                    output::print_records(*format, &report.largest_files)?
+                }
+            }
+        }
+        Commands::Stats {
+            command: None,
+            record,
+            include_ignored,
+            top,
+            filter,
+        } => {
+            let filter = filter.to_filter()?;
+            if *record && (!filter.is_empty() || *include_ignored) {
+                anyhow::bail!(
+                    "--record snapshots the whole workspace with the default scan; drop the filters"
+                );
+            }
+            let stats = stats::get_stats(&config, &filter, *include_ignored)?;
+            stats::print_stats(&stats, &config.resolve_path("workspace"), *top);
+            if *record {
+                stats::record_snapshot(&config, &stats)?;
+                info!("Snapshot recorded.");
+            }
+        }
+        Commands::Git { command } => {
+            let (op, repos) = match command {
+                GitCommands::Fetch { repos } => (git::GitOp::Fetch, repos),
+                GitCommands::Pull { ff_only, repos } => {
+                    (git::GitOp::Pull { ff_only: *ff_only }, repos)
+                }
+                GitCommands::Push { repos } => (git::GitOp::Push, repos),
+            };
+            let report = git::run_bulk(&config, op, &repos.to_filter(), repos.jobs)?;
+
+            if report.workspace_not_found {
+                error!("Workspace not found.");
+                return Ok(());
+            }
+            if report.results.is_empty() {
+                warn!("No matching git repositories.");
+                return Ok(());
+            }
+
+            println!("\n{:<22} {:<4} {:<10} Detail", "Project", "", "Sync");
+            println!("{}", "-".repeat(80));
+            for r in &report.results {
+                println!(
+                    "{:<22} {:<4} {:<10} {}",
+                    output::truncate(&r.name, 22),
+                    if r.success { "✓" } else { "✗" },
+                    r.sync,
+                    r.detail
+                );
+            }
+
+            let failed = report.failed();
+            if failed > 0 {
+                anyhow::bail!("{} of {} repositories failed", failed, report.results.len());
+            }
+            info!("✓ {} repositories done", report.results.len());
+        }
+        Commands::Project { command } => match command {
+            ProjectCommands::Tag { tags, remove, path } => {
+                let manifest = project::tag_project(path, tags, *remove)?;
+                info!("Tags: {}", manifest.tags.join(", "));
+            }
+            ProjectCommands::Set { key, value, path } => {
+                project::set_field(path, key, value)?;
+                info!("Updated {} for {:?}", key, project::project_root(path)?);
+            }
+            ProjectCommands::Archive {
+                name,
+                compress,
+                force,
+            } => {
+                let moved = project::archive_project(&config, name, *compress, *force)?;
+                info!(
+                    "✓ Archived {} -> {:?}{}",
+                    name,
+                    moved.destination,
+                    if moved.compressed {
+                        " (compressed)"
+                    } else {
+                        ""
+                    }
+                );
+            }
+            ProjectCommands::Restore { name } => {
+                let moved = project::restore_project(&config, name)?;
+                info!("✓ Restored {} -> {:?}", name, moved.destination);
+            }
+            ProjectCommands::Move { name, to } => {
+                let moved = project::move_project(&config, name, to)?;
+                info!("✓ Moved {} -> {:?}", name, moved.destination);
+            }
+            ProjectCommands::Show { path, format } => {
+                let root = project::project_root(path)?;
+                let manifest = ProjectManifest::load(&root)?;
+                if !format.is_human() {
+                    output::print_json(&manifest)?;
+                } else if let Some(m) = manifest {
+                    project::print_manifest(&root, &m);
+                } else {
+                    warn!(
+                        "No manifest in {:?}. Create one with `wardex project set` or `wardex project tag`.",
+                        root
+                    );
+                }
+            }
+        },
         Commands::Dashboard => {
             tui::run(&config)?;
         }
-        Commands::Info { path } => {
+        Commands::Info { path, format } => {
             let target = path.clone().unwrap_or_else(|| PathBuf::from("."));
-            info!("Info for: {:?}", target);
-            if target.exists() {
-                let meta = fs_err::metadata(&target)?;
-                println!(
-                    "Type: {:?}",
-                    if target.is_dir() { "Directory" } else { "File" }
-                );
-                println!("Size: {} bytes", meta.len());
-                println!("Modified: {:?}", meta.modified()?);
+            if !target.exists() {
+                anyhow::bail!("Path not found: {:?}", target);
+            }
+            let info = info::inspect(&config, &target)?;
+            if format.is_human() {
+                info::print_info(&info);
             } else {
-                error!("Path not found");
+                output::print_json(&info)?;
             }
         }
+        Commands::Shell { command } => {
+            handle_shell_command(command, cli.config.as_ref())?;
+        }
         Commands::Config { command } => {
             handle_config_command(&config, command, cli.config.as_ref())?;
         }
@@ -523,6 +1617,20 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Redraw a single progress line on stderr until `done` is set
+fn render_search_progress(progress: &search::SearchProgress, done: &AtomicBool) {
+    while !done.load(Ordering::Relaxed) {
+        eprint!(
+            "\r\x1b[2KScanned {} files ({:.1} MB)",
+            progress.files_scanned.load(Ordering::Relaxed),
+            progress.bytes_scanned.load(Ordering::Relaxed) as f64 / 1024.0 / 1024.0
+        );
+        let _ = std::io::stderr().flush();
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    eprint!("\r\x1b[2K");
+}
+
 fn handle_config_command(
     config: &Config,
     command: &ConfigCommands,
@@ -617,9 +1725,9 @@ ctf:
                 "archives" => config.resolve_path("archives"),
                 "ctf" => config.ctf_root(),
                 _ => anyhow::bail!(
-                    "Unknown folder: {}\n\n\
-                    Available: workspace, inbox, projects, areas, resources, archives, ctf",
-                    folder
+                    "Unknown folder: {}\n\nAvailable: {}",
+                    folder,
+                    FOLDER_KEYS.join(", ")
                 ),
             };
 
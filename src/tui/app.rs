@@ -1,6 +1,8 @@
 use crate::config::Config;
+use crate::engine::project::ProjectInfo;
 use crate::engine::stats::WorkspaceStats;
 use crate::engine::status::RepoStatus;
+use ratatui::widgets::ListState;
 
 pub enum CurrentScreen {
     Dashboard,
@@ -16,7 +18,15 @@ pub struct App {
     // State
     pub stats: Option<WorkspaceStats>,
     pub repos: Option<Vec<RepoStatus>>,
+    pub projects: Option<Vec<ProjectInfo>>,
     pub is_loading: bool,
+
+    // Projects screen
+    /// Filter text: plain words match name/description, `tag:`, `status:`
+    /// and `lang:` tokens match manifest fields
+    pub project_filter: String,
+    pub editing_filter: bool,
+    pub project_list: ListState,
 }
 
 impl App {
@@ -27,11 +37,77 @@ impl App {
             should_quit: false,
             stats: None,
             repos: None,
+            projects: None,
             is_loading: false,
+            project_filter: String::new(),
+            editing_filter: false,
+            project_list: ListState::default(),
         }
     }
 
     pub fn on_tick(&mut self) {
         // Handle tick events (e.g. spinner animation)
     }
+
+    pub fn next_screen(&mut self) {
+        self.current_screen = match self.current_screen {
+            CurrentScreen::Dashboard => CurrentScreen::Projects,
+            CurrentScreen::Projects => CurrentScreen::CTFs,
+            CurrentScreen::CTFs => CurrentScreen::Dashboard,
+        };
+    }
+
+    pub fn previous_screen(&mut self) {
+        self.current_screen = match self.current_screen {
+            CurrentScreen::Dashboard => CurrentScreen::CTFs,
+            CurrentScreen::Projects => CurrentScreen::Dashboard,
+            CurrentScreen::CTFs => CurrentScreen::Projects,
+        };
+    }
+
+    /// Projects matching the current filter
+    pub fn visible_projects(&self) -> Vec<&ProjectInfo> {
+        let tokens: Vec<String> = self
+            .project_filter
+            .split_whitespace()
+            .map(|t| t.to_lowercase())
+            .collect();
+
+        self.projects
+            .iter()
+            .flatten()
+            .filter(|p| tokens.iter().all(|t| project_matches(p, t)))
+            .collect()
+    }
+
+    pub fn move_project_selection(&mut self, delta: isize) {
+        let len = self.visible_projects().len();
+        if len == 0 {
+            self.project_list.select(None);
+            return;
+        }
+        let current = self.project_list.selected().unwrap_or(0) as isize;
+        self.project_list
+            .select(Some((current + delta).clamp(0, len as isize - 1) as usize));
+    }
+}
+
+fn project_matches(project: &ProjectInfo, token: &str) -> bool {
+    let manifest = project.manifest.as_ref();
+    let field_eq =
+        |value: Option<&str>, wanted: &str| value.is_some_and(|v| v.eq_ignore_ascii_case(wanted));
+
+    if let Some(tag) = token.strip_prefix("tag:") {
+        manifest.is_some_and(|m| m.has_tag(tag))
+    } else if let Some(status) = token.strip_prefix("status:") {
+        let current = manifest.and_then(|m| m.status).map(|s| s.to_string());
+        field_eq(current.as_deref(), status)
+    } else if let Some(lang) = token.strip_prefix("lang:") {
+        field_eq(manifest.and_then(|m| m.language.as_deref()), lang)
+    } else {
+        project.name.to_lowercase().contains(token)
+            || manifest
+                .and_then(|m| m.description.as_ref())
+                .is_some_and(|d| d.to_lowercase().contains(token))
+    }
 }
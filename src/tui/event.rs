@@ -1,3 +1,4 @@
+use crate::engine::project::ProjectInfo;
 use crate::engine::stats::WorkspaceStats;
 use crate::engine::status::RepoStatus;
 use crossterm::event::{self, Event as CrosstermEvent, KeyEvent, MouseEvent};
@@ -22,8 +23,9 @@ pub enum Event {
 
 #[derive(Debug)]
 pub enum DataEvent {
-    Stats(WorkspaceStats),
+    Stats(Box<WorkspaceStats>),
     GitStatus(Vec<RepoStatus>),
+    Projects(Vec<ProjectInfo>),
 }
 
 #[derive(Debug)]
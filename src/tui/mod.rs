@@ -1,5 +1,6 @@
 pub mod app;
 pub mod event;
+pub mod picker;
 pub mod ui;
 pub mod update;
 
@@ -34,21 +35,33 @@ pub fn run(config: &Config) -> Result<()> {
     let sender = events.sender.clone();
     let config_clone = config.clone();
     thread::spawn(move || {
-        if let Ok(stats) = crate::engine::stats::get_stats(&config_clone) {
-            sender.send(Event::Data(DataEvent::Stats(stats))).ok();
+        if let Ok(stats) =
+            crate::engine::stats::get_stats(&config_clone, &Default::default(), false)
+        {
+            sender
+                .send(Event::Data(DataEvent::Stats(Box::new(stats))))
+                .ok();
         }
     });
 
     let sender = events.sender.clone();
     let config_clone = config.clone();
     thread::spawn(move || {
-        if let Ok(report) = crate::engine::status::show_status(&config_clone) {
+        if let Ok(report) = crate::engine::status::show_status(&config_clone, false) {
             sender
                 .send(Event::Data(DataEvent::GitStatus(report.repos)))
                 .ok();
         }
     });
 
+    let sender = events.sender.clone();
+    let config_clone = config.clone();
+    thread::spawn(move || {
+        if let Ok(projects) = crate::engine::project::list_projects(&config_clone) {
+            sender.send(Event::Data(DataEvent::Projects(projects))).ok();
+        }
+    });
+
     // Main loop
     let res = run_app(&mut terminal, &mut app, &events);
 
@@ -85,8 +98,9 @@ fn run_app(
                 }
             }
             Event::Data(data) => match data {
-                DataEvent::Stats(stats) => app.stats = Some(stats),
+                DataEvent::Stats(stats) => app.stats = Some(*stats),
                 DataEvent::GitStatus(repos) => app.repos = Some(repos),
+                DataEvent::Projects(projects) => app.projects = Some(projects),
             },
             Event::Tick => {}
             _ => {}
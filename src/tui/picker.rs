@@ -0,0 +1,204 @@
+use anyhow::Result;
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame, Terminal,
+};
+use std::io;
+use std::path::PathBuf;
+
+use crate::engine::search::{rank_projects, ProjectCandidate, SearchResult};
+
+/// Interactive fuzzy picker state
+struct Picker<'a> {
+    candidates: &'a [ProjectCandidate],
+    query: String,
+    results: Vec<SearchResult>,
+    list_state: ListState,
+}
+
+impl<'a> Picker<'a> {
+    fn new(candidates: &'a [ProjectCandidate], query: &str) -> Self {
+        let mut picker = Self {
+            candidates,
+            query: query.to_string(),
+            results: Vec::new(),
+            list_state: ListState::default(),
+        };
+        picker.refresh();
+        picker
+    }
+
+    fn refresh(&mut self) {
+        self.results = rank_projects(self.candidates, &self.query);
+        self.list_state
+            .select((!self.results.is_empty()).then_some(0));
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if self.results.is_empty() {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0) as isize;
+        let last = self.results.len() as isize - 1;
+        self.list_state
+            .select(Some((current + delta).clamp(0, last) as usize));
+    }
+
+    fn selected(&self) -> Option<PathBuf> {
+        self.list_state
+            .selected()
+            .and_then(|i| self.results.get(i))
+            .map(|r| r.path.clone())
+    }
+}
+
+enum Outcome {
+    Continue,
+    Picked(Option<PathBuf>),
+}
+
+/// Let the user narrow down `candidates` by typing and pick one.
+///
+/// Drawn on stderr so stdout stays free for the selected path, which makes
+/// `cd "$(wardex find --pick)"` work. Returns `None` if cancelled.
+pub fn pick(candidates: &[ProjectCandidate], query: &str) -> Result<Option<PathBuf>> {
+    enable_raw_mode()?;
+    let mut stderr = io::stderr();
+    execute!(stderr, EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stderr))?;
+
+    let mut picker = Picker::new(candidates, query);
+    let res = run_picker(&mut terminal, &mut picker);
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+
+    res
+}
+
+fn run_picker(
+    terminal: &mut Terminal<CrosstermBackend<io::Stderr>>,
+    picker: &mut Picker,
+) -> Result<Option<PathBuf>> {
+    loop {
+        terminal.draw(|f| render(picker, f))?;
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            if let Outcome::Picked(path) = handle_key(picker, key) {
+                return Ok(path);
+            }
+        }
+    }
+}
+
+fn handle_key(picker: &mut Picker, key: KeyEvent) -> Outcome {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Esc => return Outcome::Picked(None),
+        KeyCode::Char('c') if ctrl => return Outcome::Picked(None),
+        KeyCode::Enter => return Outcome::Picked(picker.selected()),
+        KeyCode::Up => picker.move_selection(-1),
+        KeyCode::Char('p') if ctrl => picker.move_selection(-1),
+        KeyCode::Down => picker.move_selection(1),
+        KeyCode::Char('n') if ctrl => picker.move_selection(1),
+        KeyCode::PageUp => picker.move_selection(-10),
+        KeyCode::PageDown => picker.move_selection(10),
+        KeyCode::Char('u') if ctrl => {
+            picker.query.clear();
+            picker.refresh();
+        }
+        KeyCode::Backspace => {
+            picker.query.pop();
+            picker.refresh();
+        }
+        KeyCode::Char(c) if !ctrl => {
+            picker.query.push(c);
+            picker.refresh();
+        }
+        _ => {}
+    }
+    Outcome::Continue
+}
+
+fn render(picker: &mut Picker, f: &mut Frame) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(3), // Query
+                Constraint::Min(0),    // Results
+                Constraint::Length(1), // Footer
+            ]
+            .as_ref(),
+        )
+        .split(f.area());
+
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled("> ", Style::default().fg(Color::Cyan)),
+        Span::raw(picker.query.as_str()),
+    ]))
+    .block(Block::default().borders(Borders::ALL).title("Find"));
+    f.render_widget(input, chunks[0]);
+    f.set_cursor_position((
+        chunks[0].x + 3 + picker.query.chars().count() as u16,
+        chunks[0].y + 1,
+    ));
+
+    let items: Vec<ListItem> = picker
+        .results
+        .iter()
+        .map(|r| {
+            let name = r
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let parent = r
+                .path
+                .parent()
+                .map(|p| p.display().to_string())
+                .unwrap_or_default();
+            ListItem::new(Line::from(vec![
+                Span::styled(name, Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw("  "),
+                Span::styled(parent, Style::default().fg(Color::DarkGray)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL))
+        .highlight_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+    f.render_stateful_widget(list, chunks[1], &mut picker.list_state);
+
+    let footer = Paragraph::new(Line::from(vec![
+        Span::raw(format!(
+            "{}/{}  ",
+            picker.results.len(),
+            picker.candidates.len()
+        )),
+        Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" select  "),
+        Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" cancel"),
+    ]));
+    f.render_widget(footer, chunks[2]);
+}
@@ -2,10 +2,11 @@ use ratatui::{
     layout::{Constraint, Direction, Layout},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Tabs},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Tabs},
     Frame,
 };
 
+use crate::engine::project::ProjectStatus;
 use crate::tui::app::{App, CurrentScreen};
 
 pub fn render(app: &mut App, f: &mut Frame) {
@@ -42,6 +43,7 @@ pub fn render(app: &mut App, f: &mut Frame) {
     // Content
     match app.current_screen {
         CurrentScreen::Dashboard => render_dashboard(app, f, chunks[1]),
+        CurrentScreen::Projects => render_projects(app, f, chunks[1]),
         _ => render_placeholder(f, chunks[1]),
     }
 
@@ -49,7 +51,9 @@ pub fn render(app: &mut App, f: &mut Frame) {
     let footer = Paragraph::new(Line::from(vec![
         Span::raw("Press "),
         Span::styled("q", Style::default().add_modifier(Modifier::BOLD)),
-        Span::raw(" to quit"),
+        Span::raw(" to quit, "),
+        Span::styled("Tab", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" to switch tabs"),
     ]));
     f.render_widget(footer, chunks[2]);
 }
@@ -139,6 +143,18 @@ fn render_dashboard(app: &App, f: &mut Frame, area: ratatui::layout::Rect) {
                         Span::styled(if r.is_dirty { "DIRTY" } else { "CLEAN" }, status_style),
                         Span::raw(" "),
                         Span::raw(sync_icon),
+                        Span::raw(" "),
+                        Span::styled(
+                            r.branch.clone().unwrap_or_default(),
+                            Style::default().fg(Color::DarkGray),
+                        ),
+                        Span::styled(
+                            r.operation
+                                .as_ref()
+                                .map(|op| format!(" {}", op.to_uppercase()))
+                                .unwrap_or_default(),
+                            Style::default().fg(Color::Yellow),
+                        ),
                     ])
                 })
                 .collect()
@@ -152,6 +168,82 @@ fn render_dashboard(app: &App, f: &mut Frame, area: ratatui::layout::Rect) {
     f.render_widget(right_block, chunks[1]);
 }
 
+fn render_projects(app: &mut App, f: &mut Frame, area: ratatui::layout::Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)].as_ref())
+        .split(area);
+
+    let filter_style = if app.editing_filter {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    };
+    let filter = Paragraph::new(Line::from(vec![
+        Span::styled("/ ", Style::default().fg(Color::Cyan)),
+        Span::styled(app.project_filter.as_str(), filter_style),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Filter (text, tag:x, status:x, lang:x)"),
+    );
+    f.render_widget(filter, chunks[0]);
+
+    let Some(all) = &app.projects else {
+        f.render_widget(
+            Paragraph::new("Loading projects...")
+                .block(Block::default().title("Projects").borders(Borders::ALL)),
+            chunks[1],
+        );
+        return;
+    };
+    let total = all.len();
+
+    let items: Vec<ListItem> = app
+        .visible_projects()
+        .into_iter()
+        .map(|p| {
+            let m = p.manifest.as_ref();
+            let (status, status_style) = match m.and_then(|m| m.status) {
+                Some(ProjectStatus::Active) => ("active", Style::default().fg(Color::Green)),
+                Some(ProjectStatus::Paused) => ("paused", Style::default().fg(Color::Yellow)),
+                Some(ProjectStatus::Done) => ("done", Style::default().fg(Color::Blue)),
+                None => ("-", Style::default().fg(Color::DarkGray)),
+            };
+            let tags = m
+                .map(|m| m.tags.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>())
+                .unwrap_or_default()
+                .join(" ");
+
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<24}", p.name),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!("{:<8}", status), status_style),
+                Span::raw(format!(
+                    "{:<12}",
+                    m.and_then(|m| m.language.as_deref()).unwrap_or("")
+                )),
+                Span::styled(format!("{:<24}", tags), Style::default().fg(Color::Magenta)),
+                Span::styled(
+                    m.and_then(|m| m.description.as_deref())
+                        .unwrap_or("")
+                        .to_string(),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+
+    let title = format!("Projects ({}/{})", items.len(), total);
+    let list = List::new(items)
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    f.render_stateful_widget(list, chunks[1], &mut app.project_list);
+}
+
 fn render_placeholder(f: &mut Frame, area: ratatui::layout::Rect) {
     let block = Block::default().title("Coming Soon").borders(Borders::ALL);
     f.render_widget(block, area);
@@ -1,4 +1,4 @@
-use crate::tui::app::App;
+use crate::tui::app::{App, CurrentScreen};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 #[derive(PartialEq)]
@@ -7,7 +7,20 @@ pub enum Action {
     Quit,
 }
 
-pub fn update(_app: &mut App, key_event: KeyEvent) -> Option<Action> {
+pub fn update(app: &mut App, key_event: KeyEvent) -> Option<Action> {
+    if app.editing_filter {
+        match key_event.code {
+            KeyCode::Enter | KeyCode::Esc => app.editing_filter = false,
+            KeyCode::Backspace => {
+                app.project_filter.pop();
+            }
+            KeyCode::Char(c) => app.project_filter.push(c),
+            _ => {}
+        }
+        app.project_list.select(Some(0));
+        return None;
+    }
+
     match key_event.code {
         KeyCode::Char('q') | KeyCode::Esc => Some(Action::Quit),
         KeyCode::Char('c') => {
@@ -17,6 +30,26 @@ pub fn update(_app: &mut App, key_event: KeyEvent) -> Option<Action> {
                 None
             }
         }
+        KeyCode::Tab | KeyCode::Right => {
+            app.next_screen();
+            None
+        }
+        KeyCode::BackTab | KeyCode::Left => {
+            app.previous_screen();
+            None
+        }
+        KeyCode::Char('/') if matches!(app.current_screen, CurrentScreen::Projects) => {
+            app.editing_filter = true;
+            None
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.move_project_selection(1);
+            None
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.move_project_selection(-1);
+            None
+        }
         _ => None,
     }
 }
@@ -1,7 +1,7 @@
 use anyhow::{Context, Result};
 use fs_extra::dir::CopyOptions as DirCopyOptions;
 use fs_extra::file::CopyOptions as FileCopyOptions;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::config::Config;
 use crate::engine::undo;
@@ -21,10 +21,8 @@ pub fn move_item(
     dest_dir: &Path,
     dry_run: bool,
 ) -> Result<MoveResult> {
-    if !dest_dir.exists() {
-        if !dry_run {
-            fs_err::create_dir_all(dest_dir).context("Failed to create destination directory")?;
-        }
+    if !dest_dir.exists() && !dry_run {
+        fs_err::create_dir_all(dest_dir).context("Failed to create destination directory")?;
     }
 
     let file_name = src.file_name().context("Invalid source path")?;
@@ -59,3 +57,86 @@ pub fn move_item(
         used_copy_fallback: false, // fs_extra handles this internally
     })
 }
+
+/// Move `src` to the user's trash and return where it ended up.
+///
+/// Follows the freedesktop.org trash layout (`$XDG_DATA_HOME/Trash` with a
+/// `.trashinfo` per item) so file managers can restore it; on macOS the item
+/// is moved to `~/.Trash`.
+pub fn move_to_trash(src: &Path) -> Result<PathBuf> {
+    let src = fs_err::canonicalize(src)?;
+    let file_name = src
+        .file_name()
+        .context("Invalid source path")?
+        .to_string_lossy()
+        .to_string();
+
+    let (files_dir, info_dir) = if cfg!(target_os = "macos") {
+        let trash = dirs::home_dir()
+            .context("Could not determine home directory")?
+            .join(".Trash");
+        (trash, None)
+    } else {
+        let trash = dirs::data_dir()
+            .context("Could not determine data directory")?
+            .join("Trash");
+        (trash.join("files"), Some(trash.join("info")))
+    };
+    fs_err::create_dir_all(&files_dir)?;
+
+    // Same-named items already in the trash get a numeric suffix
+    let mut name = file_name.clone();
+    let mut n = 1;
+    while files_dir.join(&name).exists()
+        || info_dir
+            .as_ref()
+            .is_some_and(|d| d.join(format!("{}.trashinfo", name)).exists())
+    {
+        name = format!("{}.{}", file_name, n);
+        n += 1;
+    }
+    let dest = files_dir.join(&name);
+
+    if let Some(info_dir) = &info_dir {
+        fs_err::create_dir_all(info_dir)?;
+        fs_err::write(
+            info_dir.join(format!("{}.trashinfo", name)),
+            format!(
+                "[Trash Info]\nPath={}\nDeletionDate={}\n",
+                percent_encode_path(&src),
+                chrono::Local::now().format("%Y-%m-%dT%H:%M:%S")
+            ),
+        )?;
+    }
+
+    if std::fs::rename(&src, &dest).is_err() {
+        // Trash on another filesystem: copy, then remove the original
+        if src.is_dir() {
+            let options = DirCopyOptions {
+                copy_inside: true,
+                ..DirCopyOptions::new()
+            };
+            fs_extra::dir::copy(&src, &dest, &options)
+                .with_context(|| format!("Failed to move {:?} to the trash", src))?;
+            fs_err::remove_dir_all(&src)?;
+        } else {
+            fs_err::copy(&src, &dest)?;
+            fs_err::remove_file(&src)?;
+        }
+    }
+
+    Ok(dest)
+}
+
+/// Percent-encode a path for a `.trashinfo` `Path=` key
+fn percent_encode_path(path: &Path) -> String {
+    let mut out = String::new();
+    for byte in path.to_string_lossy().bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-_.~".contains(&byte) {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    out
+}
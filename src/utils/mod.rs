@@ -1 +1,3 @@
 pub mod fs;
+pub mod output;
+pub mod shell;
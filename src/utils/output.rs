@@ -0,0 +1,127 @@
+use anyhow::Result;
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::io::{IsTerminal, Write};
+
+/// Output format for commands that produce machine-readable results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text (default)
+    #[default]
+    Human,
+    /// A single JSON document
+    Json,
+    /// One JSON object per line
+    Ndjson,
+    /// Comma-separated values with a header row
+    Csv,
+}
+
+impl OutputFormat {
+    pub fn is_human(&self) -> bool {
+        *self == OutputFormat::Human
+    }
+}
+
+/// Value parser for `--format` on commands that print a single document
+/// rather than a list of records, so only human and JSON output apply
+pub fn document_format() -> impl TypedValueParser<Value = OutputFormat> {
+    PossibleValuesParser::new(["human", "json"])
+        .map(|s| OutputFormat::from_str(&s, false).expect("listed formats are valid"))
+}
+
+/// When to emit ANSI colors in human output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ColorChoice {
+    /// Color when stdout is a terminal and `NO_COLOR` is unset
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub fn enabled(&self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+            }
+        }
+    }
+}
+
+/// Format a byte count with a binary unit, e.g. `1.5 MB`
+pub fn human_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Short age like `5m`, `3h`, `12d` or `2y` for a Unix timestamp
+pub fn format_age(timestamp: i64) -> String {
+    let secs = (chrono::Utc::now().timestamp() - timestamp).max(0);
+    match secs {
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s < 86400 => format!("{}h", s / 3600),
+        s if s < 365 * 86400 => format!("{}d", s / 86400),
+        s => format!("{}y", s / (365 * 86400)),
+    }
+}
+
+/// Shorten `text` to `max` characters, ending in `…` when cut
+pub fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        let cut: String = text.chars().take(max - 1).collect();
+        format!("{}…", cut)
+    }
+}
+
+/// Print a value as pretty JSON to stdout
+pub fn print_json<T: Serialize + ?Sized>(value: &T) -> Result<()> {
+    let mut out = std::io::stdout().lock();
+    serde_json::to_writer_pretty(&mut out, value)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+/// Print a single value as one NDJSON line to stdout
+pub fn print_ndjson_line<T: Serialize + ?Sized>(value: &T) -> Result<()> {
+    let mut out = std::io::stdout().lock();
+    serde_json::to_writer(&mut out, value)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+/// Print records as CSV to stdout. Records must serialize to flat structs.
+pub fn print_csv<T: Serialize>(records: &[T]) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(std::io::stdout().lock());
+    for record in records {
+        writer.serialize(record)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Print a list of records in a non-human format
+pub fn print_records<T: Serialize>(format: OutputFormat, records: &[T]) -> Result<()> {
+    match format {
+        OutputFormat::Json => print_json(records),
+        OutputFormat::Ndjson => records.iter().try_for_each(|r| print_ndjson_line(r)),
+        OutputFormat::Csv => print_csv(records),
+        OutputFormat::Human => Ok(()),
+    }
+}
@@ -0,0 +1,236 @@
+use crate::config::{Config, FOLDER_KEYS};
+use crate::core::history::JumpHistory;
+use crate::engine::{ctf, search};
+use anyhow::Result;
+use clap::ValueEnum;
+use std::fs;
+
+/// Shells supported by `wardex shell init`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ShellKind {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl ShellKind {
+    pub fn clap_shell(&self) -> clap_complete::Shell {
+        match self {
+            ShellKind::Bash => clap_complete::Shell::Bash,
+            ShellKind::Zsh => clap_complete::Shell::Zsh,
+            ShellKind::Fish => clap_complete::Shell::Fish,
+        }
+    }
+}
+
+/// Values completed at runtime by `wardex shell complete`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CompletionKind {
+    /// CTF event names
+    Events,
+    /// Challenge names of an event (active or latest by default)
+    Challenges,
+    /// Folder keys accepted by `config goto`
+    Folders,
+    /// Project directory names known to `find`
+    Projects,
+}
+
+/// Candidates for a dynamic completion, one per entry
+pub fn complete(config: &Config, kind: CompletionKind, event: Option<&str>) -> Result<Vec<String>> {
+    let mut values = match kind {
+        CompletionKind::Events => ctf::list_events(config)?
+            .events
+            .into_iter()
+            .map(|e| e.name)
+            .collect(),
+        CompletionKind::Challenges => {
+            let event_path = ctf::get_event_path(config, event, None)?;
+            let mut names = Vec::new();
+            for category in fs::read_dir(event_path)?.flatten() {
+                if !category.path().is_dir() || is_hidden(&category) {
+                    continue;
+                }
+                for chall in fs::read_dir(category.path())?.flatten() {
+                    if chall.path().is_dir() && !is_hidden(&chall) {
+                        names.push(chall.file_name().to_string_lossy().to_string());
+                    }
+                }
+            }
+            names
+        }
+        CompletionKind::Folders => FOLDER_KEYS.iter().map(|k| k.to_string()).collect(),
+        CompletionKind::Projects => search::project_candidates(config, &JumpHistory::load())
+            .into_iter()
+            .map(|c| c.name)
+            .collect(),
+    };
+    values.sort();
+    values.dedup();
+    Ok(values)
+}
+
+fn is_hidden(entry: &fs::DirEntry) -> bool {
+    entry.file_name().to_string_lossy().starts_with('.')
+}
+
+/// Integration script: clap-generated static completions for `wardex`,
+/// followed by the `wx` wrapper and the dynamic completion hooks.
+pub fn init_script(shell: ShellKind, static_completions: &str) -> String {
+    let integration = match shell {
+        ShellKind::Bash => BASH,
+        ShellKind::Zsh => ZSH,
+        ShellKind::Fish => FISH,
+    };
+    format!("{}\n{}", static_completions, integration)
+}
+
+const BASH: &str = r#"# --- wardex shell integration ---
+# Load with: eval "$(wardex shell init bash)"
+
+wx() {
+    local dir
+    case "$1" in
+        goto)
+            dir="$(wardex config goto "${2:-workspace}")" && cd "$dir"
+            ;;
+        cd)
+            shift
+            if [ $# -eq 0 ]; then
+                dir="$(wardex find --pick)"
+            else
+                dir="$(wardex find "$*")"
+            fi
+            [ -n "$dir" ] && cd "$dir"
+            ;;
+        ctf)
+            if [ "$2" = "goto" ]; then
+                dir="$(wardex ctf path "${@:3}")" && cd "$dir"
+            else
+                wardex "$@"
+            fi
+            ;;
+        *)
+            wardex "$@"
+            ;;
+    esac
+}
+
+_wardex_dynamic() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    local args=("${COMP_WORDS[@]:1:COMP_CWORD-1}")
+    local kind=""
+    case "${#args[@]}:${args[*]}" in
+        "1:goto" | "2:config goto") kind=folders ;;
+        "1:cd") kind=projects ;;
+        "2:ctf goto" | "2:ctf path" | "2:ctf use" | "2:ctf archive") kind=events ;;
+        "3:ctf goto "* | "3:ctf path "*) kind=challenges ;;
+    esac
+
+    if [ -n "$kind" ]; then
+        local IFS=$'\n'
+        COMPREPLY=($(compgen -W "$(wardex shell complete "$kind" "${args[@]:2}" 2>/dev/null)" -- "$cur"))
+        return
+    fi
+
+    COMP_WORDS[0]=wardex
+    _wardex wardex "$2" "$3"
+}
+
+complete -F _wardex_dynamic -o bashdefault -o default wardex wx
+"#;
+
+const ZSH: &str = r#"# --- wardex shell integration ---
+# Load with: eval "$(wardex shell init zsh)"  (after compinit)
+
+wx() {
+    local dir
+    case "$1" in
+        goto)
+            dir="$(wardex config goto "${2:-workspace}")" && cd "$dir"
+            ;;
+        cd)
+            shift
+            if [ $# -eq 0 ]; then
+                dir="$(wardex find --pick)"
+            else
+                dir="$(wardex find "$*")"
+            fi
+            [ -n "$dir" ] && cd "$dir"
+            ;;
+        ctf)
+            if [ "$2" = "goto" ]; then
+                dir="$(wardex ctf path "${@:3}")" && cd "$dir"
+            else
+                wardex "$@"
+            fi
+            ;;
+        *)
+            wardex "$@"
+            ;;
+    esac
+}
+
+_wardex_dynamic() {
+    local -a args=("${(@)words[2,CURRENT-1]}")
+    local kind=""
+    case "${#args}:${args[*]}" in
+        "1:goto" | "2:config goto") kind=folders ;;
+        "1:cd") kind=projects ;;
+        "2:ctf goto" | "2:ctf path" | "2:ctf use" | "2:ctf archive") kind=events ;;
+        "3:ctf goto "* | "3:ctf path "*) kind=challenges ;;
+    esac
+
+    if [[ -n "$kind" ]]; then
+        local -a candidates=("${(@f)$(wardex shell complete "$kind" "${(@)args[3,-1]}" 2>/dev/null)}")
+        candidates=(${candidates:#})
+        compadd -a candidates
+        return
+    fi
+
+    words[1]=wardex
+    _wardex "$@"
+}
+
+compdef _wardex_dynamic wardex wx
+"#;
+
+const FISH: &str = r#"# --- wardex shell integration ---
+# Load with: wardex shell init fish | source
+
+function wx
+    switch "$argv[1]"
+        case goto
+            set -q argv[2]; or set argv[2] workspace
+            set -l dir (wardex config goto $argv[2]); and cd $dir
+        case cd
+            if set -q argv[2]
+                set -l dir (wardex find (string join ' ' -- $argv[2..-1])); and cd $dir
+            else
+                set -l dir (wardex find --pick); and test -n "$dir"; and cd $dir
+            end
+        case ctf
+            if test "$argv[2]" = goto
+                set -l dir (wardex ctf path $argv[3..-1]); and cd $dir
+            else
+                wardex $argv
+            end
+        case '*'
+            wardex $argv
+    end
+end
+
+function __wardex_args
+    set -l tokens (commandline -opc)
+    string join ' ' -- $tokens[2..-1]
+end
+
+complete -c wx -w wardex
+complete -c wx -f -n 'string match -q -- goto (__wardex_args)' -a '(wardex shell complete folders 2>/dev/null)'
+complete -c wx -f -n 'string match -q -- cd (__wardex_args)' -a '(wardex shell complete projects 2>/dev/null)'
+complete -c wardex -f -n 'string match -q -- "config goto" (__wardex_args)' -a '(wardex shell complete folders 2>/dev/null)'
+for cmd in wardex wx
+    complete -c $cmd -f -n 'string match -q -r "^ctf (goto|path|use|archive)\$" -- (__wardex_args)' -a '(wardex shell complete events 2>/dev/null)'
+    complete -c $cmd -f -n 'string match -q -r "^ctf (goto|path) \S+\$" -- (__wardex_args)' -a '(wardex shell complete challenges (commandline -opc)[4] 2>/dev/null)'
+end
+"#;
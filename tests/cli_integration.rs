@@ -1,3 +1,4 @@
+use assert_cmd::cargo::cargo_bin_cmd;
 use assert_cmd::Command;
 use predicates::prelude::*;
 use std::fs;
@@ -14,11 +15,12 @@ impl TestEnv {
     }
 
     fn cmd(&self) -> Command {
-        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let mut cmd = cargo_bin_cmd!();
         cmd.current_dir(self.temp_dir.path());
         cmd.env("WX_PATHS_WORKSPACE", self.temp_dir.path());
         cmd.env("XDG_CONFIG_HOME", self.temp_dir.path());
         cmd.env("XDG_DATA_HOME", self.temp_dir.path());
+        cmd.env("XDG_CACHE_HOME", self.temp_dir.path().join(".cache"));
         cmd.env("HOME", self.temp_dir.path()); // Just in case
         let config_file = self.temp_dir.path().join("config.yaml");
         if config_file.exists() {
@@ -74,7 +76,7 @@ ctf:
 
 #[test]
 fn test_cli_help() {
-    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    let mut cmd = cargo_bin_cmd!();
     cmd.arg("--help")
         .assert()
         .success()
@@ -83,7 +85,7 @@ fn test_cli_help() {
 
 #[test]
 fn test_cli_version() {
-    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    let mut cmd = cargo_bin_cmd!();
     cmd.arg("--version")
         .assert()
         .success()
@@ -96,7 +98,7 @@ fn test_ctf_list_empty() {
     env.setup_workspace();
     env.create_config();
 
-    env.cmd().args(&["ctf", "list"]).assert().success();
+    env.cmd().args(["ctf", "list"]).assert().success();
 }
 
 #[test]
@@ -105,7 +107,7 @@ fn test_config_init() {
     // Do NOT create config first
     // env.create_config();
 
-    env.cmd().args(&["config", "init"]).assert().success();
+    env.cmd().args(["config", "init"]).assert().success();
 }
 
 #[test]
@@ -116,7 +118,7 @@ fn test_config_init_twice_without_force_fails() {
 
     // This should fail because it exists
     env.cmd()
-        .args(&["config", "init"])
+        .args(["config", "init"])
         .assert()
         .failure()
         .stderr(predicate::str::contains("already exists"));
@@ -128,11 +130,11 @@ fn test_config_init_with_force_succeeds() {
     env.create_config();
 
     // First attempt fails
-    env.cmd().args(&["config", "init"]).assert().failure();
+    env.cmd().args(["config", "init"]).assert().failure();
 
     // Force succeeds
     env.cmd()
-        .args(&["config", "init", "--force"])
+        .args(["config", "init", "--force"])
         .assert()
         .success();
 }
@@ -143,7 +145,7 @@ fn test_config_goto_workspace() {
     env.create_config();
 
     env.cmd()
-        .args(&["config", "goto", "workspace"])
+        .args(["config", "goto", "workspace"])
         .assert()
         .success()
         .stdout(predicate::str::contains(env.path().to_str().unwrap()));
@@ -155,7 +157,7 @@ fn test_config_goto_invalid_folder() {
     env.create_config();
 
     env.cmd()
-        .args(&["config", "goto", "invalid"])
+        .args(["config", "goto", "invalid"])
         .assert()
         .failure()
         .stderr(predicate::str::contains("Unknown folder"));
@@ -185,7 +187,7 @@ fn test_ctf_init_creates_event() {
     std::env::set_current_dir(env.path().join("1_Projects/CTFs")).unwrap();
 
     env.cmd()
-        .args(&["ctf", "init", "TestEvent"])
+        .args(["ctf", "init", "TestEvent"])
         .assert()
         .success();
     // .stderr(predicate::str::contains("Initialized")); // REMOVED: Flaky on Nix build
@@ -207,7 +209,7 @@ fn test_ctf_init_with_date() {
     std::env::set_current_dir(env.path().join("1_Projects/CTFs")).unwrap();
 
     env.cmd()
-        .args(&["ctf", "init", "TestEvent", "--date", "2024-12-25"])
+        .args(["ctf", "init", "TestEvent", "--date", "2024-12-25"])
         .assert()
         .success();
 
@@ -226,7 +228,7 @@ fn test_ctf_add_invalid_format() {
     env.create_config();
 
     env.cmd()
-        .args(&["ctf", "add", "invalid-format"])
+        .args(["ctf", "add", "invalid-format"])
         .assert()
         .failure();
 }
@@ -245,13 +247,13 @@ fn test_ctf_path_command() {
     // Config points to it.
 
     env.cmd()
-        .args(&["ctf", "init", "PathTest"])
+        .args(["ctf", "init", "PathTest"])
         .assert()
         .success();
 
     // Get path
     env.cmd()
-        .args(&["ctf", "path", "PathTest"])
+        .args(["ctf", "path", "PathTest"])
         .assert()
         .success()
         .stdout(predicate::str::contains("PathTest"));
@@ -267,11 +269,11 @@ fn test_ctf_path_command() {
     // Add challenge needs to be run inside event dir
     let mut cmd = env.cmd();
     cmd.current_dir(&event_dir);
-    cmd.args(&["ctf", "add", "web/chall1"]).assert().success();
+    cmd.args(["ctf", "add", "web/chall1"]).assert().success();
 
     // Test path to challenge
     env.cmd()
-        .args(&["ctf", "path", "PathTest", "chall1"])
+        .args(["ctf", "path", "PathTest", "chall1"])
         .assert()
         .success()
         .stdout(predicate::str::contains("chall1"));
@@ -287,7 +289,7 @@ fn test_ctf_import_with_category_flag_and_move() {
     std::fs::create_dir_all(&ctf_root).unwrap();
 
     env.cmd()
-        .args(&["ctf", "init", "ImportTest"])
+        .args(["ctf", "init", "ImportTest"])
         .assert()
         .success();
 
@@ -306,7 +308,7 @@ fn test_ctf_import_with_category_flag_and_move() {
     // Import with category override
     let mut cmd = env.cmd();
     cmd.current_dir(&event_dir);
-    cmd.args(&[
+    cmd.args([
         "ctf",
         "import",
         import_file.to_str().unwrap(),
@@ -340,7 +342,7 @@ fn test_ctf_context_awareness() {
 
     // Init event
     env.cmd()
-        .args(&["ctf", "init", "ContextTest"])
+        .args(["ctf", "init", "ContextTest"])
         .assert()
         .success();
 
@@ -357,7 +359,7 @@ fn test_ctf_context_awareness() {
 
     let mut cmd = env.cmd();
     cmd.current_dir(&web_dir);
-    cmd.args(&["ctf", "add", "chall1"]) // Should infer "web"
+    cmd.args(["ctf", "add", "chall1"]) // Should infer "web"
         .assert()
         .success();
 
@@ -365,8 +367,8 @@ fn test_ctf_context_awareness() {
 
     // 2. Test info command from deep inside
     let mut cmd = env.cmd();
-    cmd.current_dir(&web_dir.join("chall1"));
-    cmd.args(&["ctf", "info"])
+    cmd.current_dir(web_dir.join("chall1"));
+    cmd.args(["ctf", "info"])
         .assert()
         .success()
         .stdout(predicate::str::contains("ContextTest"))
@@ -382,7 +384,7 @@ fn test_ctf_global_state() {
     // 1. Init event (should auto-set global state)
     let output = env
         .cmd()
-        .args(&["ctf", "init", "GlobalEvent"])
+        .args(["ctf", "init", "GlobalEvent"])
         .output()
         .unwrap();
     assert!(output.status.success());
@@ -398,7 +400,7 @@ fn test_ctf_global_state() {
     // 2. Check info from OUTSIDE the event dir (e.g. root workspace)
     let mut cmd = env.cmd();
     cmd.current_dir(env.path()); // Workspace root, not event dir
-    cmd.args(&["ctf", "info"])
+    cmd.args(["ctf", "info"])
         .assert()
         .success()
         .stdout(predicate::str::contains("GlobalEvent"))
@@ -407,7 +409,7 @@ fn test_ctf_global_state() {
     // 3. Add challenge from outside (using global state)
     let mut cmd = env.cmd();
     cmd.current_dir(env.path());
-    cmd.args(&["ctf", "add", "pwn/remote-exploit"])
+    cmd.args(["ctf", "add", "pwn/remote-exploit"])
         .assert()
         .success();
 
@@ -425,25 +427,1816 @@ fn test_ctf_global_state() {
 
     // 4. Create another event and switch to it
     env.cmd()
-        .args(&["ctf", "init", "SecondEvent"])
+        .args(["ctf", "init", "SecondEvent"])
         .assert()
         .success();
     // Verify switch
     env.cmd()
-        .args(&["ctf", "info"])
+        .args(["ctf", "info"])
         .assert()
         .success()
         .stdout(predicate::str::contains("SecondEvent"));
 
     // 5. Explicitly use the first event
     env.cmd()
-        .args(&["ctf", "use", "GlobalEvent"])
+        .args(["ctf", "use", "GlobalEvent"])
         .assert()
         .success();
 
     env.cmd()
-        .args(&["ctf", "info"])
+        .args(["ctf", "info"])
         .assert()
         .success()
         .stdout(predicate::str::contains("GlobalEvent"));
 }
+
+#[test]
+fn test_search_finds_flags_across_tree() {
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+
+    let chall = env.path().join("1_Projects/chall");
+    fs::create_dir_all(chall.join("nested/deeper")).unwrap();
+    fs::write(chall.join("notes.txt"), "nothing here\nflag{top_level}\n").unwrap();
+    fs::write(chall.join("nested/deeper/out.log"), "CTF{deep_one}").unwrap();
+
+    env.cmd()
+        .args(["search", chall.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("flag{top_level}"))
+        .stdout(predicate::str::contains("CTF{deep_one}"));
+}
+
+#[test]
+fn test_search_json_format() {
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+
+    let chall = env.path().join("1_Projects/chall");
+    fs::create_dir_all(&chall).unwrap();
+    fs::write(chall.join("a.txt"), "flag{json_out}").unwrap();
+
+    let output = env
+        .cmd()
+        .args(["search", chall.to_str().unwrap(), "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["files_scanned"], 1);
+    assert_eq!(report["files_skipped"], 0);
+    assert!(report["errors"].as_array().unwrap().is_empty());
+    assert_eq!(report["matches"][0]["matched_text"], "flag{json_out}");
+}
+
+#[test]
+fn test_grep_ndjson_and_csv_formats() {
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+
+    fs::write(
+        env.path().join("1_Projects/readme.md"),
+        "needle one\nhay\nneedle two\n",
+    )
+    .unwrap();
+
+    let output = env
+        .cmd()
+        .args(["grep", "needle", "--format", "ndjson"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[1]["line_number"], 3);
+
+    env.cmd()
+        .args(["grep", "needle", "--format", "csv"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "file_path,line_number,matched_text,archive_entry",
+        ));
+}
+
+#[test]
+fn test_grep_uses_index_and_sees_changes_since_indexing() {
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+
+    let notes = env.path().join("1_Projects/notes");
+    fs::create_dir_all(&notes).unwrap();
+    fs::write(
+        notes.join("a.txt"),
+        "alpha needle
+",
+    )
+    .unwrap();
+    fs::write(
+        notes.join("b.txt"),
+        "nothing here
+",
+    )
+    .unwrap();
+
+    env.cmd()
+        .arg("index")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Indexed 2 files (2 read"));
+
+    env.cmd()
+        .args(["grep", "needle"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("a.txt:1: alpha needle"))
+        .stderr(predicate::str::contains("grepping 1 of 2 files"));
+
+    // Edited and newly created files are picked up without re-indexing
+    fs::write(
+        notes.join("b.txt"),
+        "now a needle too
+second line
+",
+    )
+    .unwrap();
+    fs::create_dir_all(env.path().join("3_Resources/new")).unwrap();
+    fs::write(
+        env.path().join("3_Resources/new/c.txt"),
+        "needle in new dir
+",
+    )
+    .unwrap();
+
+    env.cmd()
+        .args(["grep", "needle"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("b.txt:1: now a needle too"))
+        .stdout(predicate::str::contains("c.txt:1: needle in new dir"));
+
+    env.cmd()
+        .arg("index")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Indexed 3 files (2 read, 1 unchanged",
+        ));
+
+    env.cmd()
+        .args(["grep", "needle", "--no-index"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("a.txt:1: alpha needle"))
+        .stdout(predicate::str::contains("c.txt:1: needle in new dir"));
+}
+
+#[test]
+fn test_grep_roots_filters_and_context() {
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+
+    fs::write(
+        env.path().join("2_Areas/notes.md"),
+        "one\ntwo\nNeedle here\nthree\nfour\n",
+    )
+    .unwrap();
+    fs::write(env.path().join("2_Areas/main.rs"), "// needles\n").unwrap();
+    fs::write(env.path().join("1_Projects/todo.md"), "needle\n").unwrap();
+
+    // Only the chosen root is searched; -i, -w, -t and context apply
+    env.cmd()
+        .args([
+            "grep", "needle", "--in", "areas", "-i", "-w", "-t", "md", "-C", "1",
+        ])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("notes.md-2- two")
+                .and(predicate::str::contains("notes.md:3: Needle here"))
+                .and(predicate::str::contains("notes.md-4- three"))
+                .and(predicate::str::contains("main.rs").not())
+                .and(predicate::str::contains("todo.md").not()),
+        );
+
+    // Globs, fixed strings and colored highlighting of the match
+    env.cmd()
+        .args(["grep", "s\\n", "-F", "--in", "areas", "-g", "*.rs"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+    env.cmd()
+        .args([
+            "grep", "needle", "--in", "areas", "-g", "!*.md", "--color", "always",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("// \x1b[1;31mneedle\x1b[0ms"));
+
+    // Per-file match limit
+    fs::write(env.path().join("1_Projects/many.txt"), "needle\n".repeat(5)).unwrap();
+    let output = env
+        .cmd()
+        .args([
+            "grep", "needle", "-m", "2", "-g", "many.txt", "--format", "ndjson",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout).lines().count(), 2);
+
+    env.cmd()
+        .args(["grep", "needle", "--in", "nowhere"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown root 'nowhere'"));
+}
+
+#[test]
+fn test_find_piped_prints_best_path_and_learns_frecency() {
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+
+    fs::create_dir_all(env.path().join("1_Projects/api")).unwrap();
+    fs::create_dir_all(env.path().join("1_Projects/legacy-api")).unwrap();
+
+    let best = |query: &str| {
+        let output = env.cmd().args(["find", query]).output().unwrap();
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+
+    assert!(best("api").ends_with("1_Projects/api"));
+
+    // Jumping to legacy-api a few times makes it win the ambiguous query
+    for _ in 0..3 {
+        assert!(best("legacy").ends_with("legacy-api"));
+    }
+    assert!(best("api").ends_with("legacy-api"));
+
+    let output = env
+        .cmd()
+        .args(["find", "api", "--format", "json"])
+        .output()
+        .unwrap();
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(results[0]["frecency"].as_f64() > results[1]["frecency"].as_f64());
+
+    env.cmd()
+        .args(["find", "zzzz"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No projects found"));
+}
+
+#[test]
+fn test_shell_init_and_dynamic_completions() {
+    let env = TestEnv::new();
+
+    // Generating the scripts doesn't need a config
+    env.cmd()
+        .args(["shell", "init", "bash"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("_wardex()")
+                .and(predicate::str::contains("wx() {"))
+                .and(predicate::str::contains("complete -F _wardex_dynamic")),
+        );
+    env.cmd()
+        .args(["shell", "init", "zsh"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "compdef _wardex_dynamic wardex wx",
+        ));
+    env.cmd()
+        .args(["shell", "init", "fish"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("complete -c wardex")
+                .and(predicate::str::contains("function wx")),
+        );
+
+    env.setup_workspace();
+    env.create_config();
+    env.cmd()
+        .args(["ctf", "init", "CompleteMe"])
+        .assert()
+        .success();
+    env.cmd()
+        .args(["shell", "complete", "events"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("CompleteMe"));
+    env.cmd()
+        .args(["shell", "complete", "folders"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("archives\nareas\nctf\n"));
+}
+
+#[test]
+fn test_project_manifest_tagging_and_filters() {
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+
+    env.cmd()
+        .args(["init", "-t", "python", "-n", "demo"])
+        .assert()
+        .success();
+    let demo = env.path().join("1_Projects/demo");
+    let manifest = fs::read_to_string(demo.join(".wardex.toml")).unwrap();
+    assert!(manifest.contains("status = \"active\""));
+    assert!(manifest.contains("language = \"python\""));
+
+    env.cmd()
+        .args(["project", "tag", "crypto", "tooling"])
+        .arg("--path")
+        .arg(demo.join("src"))
+        .assert()
+        .success();
+    env.cmd()
+        .args(["project", "set", "description", "Crypto helpers", "--path"])
+        .arg(&demo)
+        .assert()
+        .success();
+    env.cmd()
+        .args(["project", "set", "status", "paused", "--path"])
+        .arg(&demo)
+        .assert()
+        .success();
+    env.cmd()
+        .args(["project", "set", "status", "someday", "--path"])
+        .arg(&demo)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown status"));
+
+    // JSON manifests are read too
+    let other = env.path().join("1_Projects/scanner");
+    fs::create_dir_all(&other).unwrap();
+    fs::write(
+        other.join(".wardex.json"),
+        r#"{"tags": ["crypto"], "status": "active", "language": "go"}"#,
+    )
+    .unwrap();
+
+    let find = |args: &[&str]| {
+        let output = env
+            .cmd()
+            .arg("find")
+            .args(args)
+            .args(["--format", "json"])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        serde_json::from_slice::<Vec<serde_json::Value>>(&output.stdout).unwrap()
+    };
+
+    // Tags are matched by the fuzzy query, and filters narrow the results
+    let results = find(&["crypto"]);
+    assert_eq!(results.len(), 2);
+    let results = find(&["--tag", "crypto", "--status", "paused"]);
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["description"], "Crypto helpers");
+    assert_eq!(results[0]["tags"], "crypto,tooling");
+    assert_eq!(find(&["--lang", "go"]).len(), 1);
+
+    env.cmd()
+        .args(["project", "show", "--format", "json"])
+        .arg(&demo)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"status\": \"paused\""));
+    env.cmd()
+        .args(["project", "show", "--format", "csv"])
+        .arg(&demo)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("possible values: human, json"));
+
+    env.cmd()
+        .args(["stats", "--tag", "crypto"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Projects:    2")
+                .and(predicate::str::contains("paused   : 1")),
+        );
+}
+
+/// Stage everything in the work tree and commit it on HEAD
+fn commit_all(repo: &git2::Repository, message: &str) -> git2::Oid {
+    let mut index = repo.index().unwrap();
+    index
+        .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+        .unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let parents: Vec<git2::Commit> = repo
+        .head()
+        .ok()
+        .and_then(|h| h.peel_to_commit().ok())
+        .into_iter()
+        .collect();
+    let parents: Vec<&git2::Commit> = parents.iter().collect();
+    repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+        .unwrap()
+}
+
+#[test]
+fn test_project_archive_restore_move_and_undo() {
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+
+    let tool = env.path().join("1_Projects/tool");
+    fs::create_dir_all(tool.join("src")).unwrap();
+    fs::write(tool.join("src/main.py"), "print('hi')\n").unwrap();
+    fs::write(tool.join(".wardex.toml"), "tags = [\"net\"]\n").unwrap();
+    let repo = git2::Repository::init(&tool).unwrap();
+
+    // Untracked files count as unsaved work
+    env.cmd()
+        .args(["project", "archive", "tool"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("uncommitted changes"));
+
+    commit_all(&repo, "init");
+
+    // So do commits that were never pushed anywhere
+    env.cmd()
+        .args(["project", "archive", "tool"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("without an upstream"));
+
+    let remote_dir = TempDir::new().unwrap();
+    let remote = format!("file://{}/remote.git", remote_dir.path().display());
+    git(remote_dir.path(), &["init", "-q", "--bare", "remote.git"]);
+    git(&tool, &["remote", "add", "origin", &remote]);
+    git(&tool, &["push", "-q", "-u", "origin", "HEAD"]);
+
+    // Names must not reach outside the PARA folders
+    env.cmd()
+        .args(["project", "archive", "../1_Projects/tool"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid project name"));
+
+    env.cmd()
+        .args(["project", "archive", "tool", "--compress"])
+        .assert()
+        .success();
+    let stub = env.path().join("4_Archives/tool");
+    assert!(!tool.exists());
+    assert!(stub.join("tool.tar.zst").exists());
+    let manifest = fs::read_to_string(stub.join(".wardex.toml")).unwrap();
+    assert!(manifest.contains("archive = \"tool.tar.zst\""));
+    assert!(manifest.contains("net"));
+
+    env.cmd()
+        .args(["project", "restore", "tool"])
+        .assert()
+        .success();
+    assert!(!stub.exists());
+    assert!(tool.join("src/main.py").exists());
+    assert!(tool.join(".git/HEAD").exists());
+
+    env.cmd()
+        .args(["project", "move", "tool", "--to", "areas"])
+        .assert()
+        .success();
+    assert!(env.path().join("2_Areas/tool/src/main.py").exists());
+    env.cmd()
+        .args(["project", "move", "tool", "--to", "archives"])
+        .assert()
+        .failure();
+
+    // Undo walks back through move, restore and compressed archive
+    env.cmd().args(["undo", "--count", "2"]).assert().success();
+    assert!(stub.join("tool.tar.zst").exists());
+    assert!(!tool.exists());
+    env.cmd().arg("undo").assert().success();
+    assert!(tool.join("src/main.py").exists());
+    assert!(!stub.exists());
+}
+
+/// Set the mtime of `path` and everything below it (except `.git`) to `days` ago
+fn age_tree(path: &std::path::Path, days: u64) {
+    let when = std::time::SystemTime::now() - std::time::Duration::from_secs(days * 86400);
+    for entry in fs::read_dir(path).unwrap().flatten() {
+        if entry.file_name() != ".git" && entry.path().is_dir() {
+            age_tree(&entry.path(), days);
+        } else if entry.path().is_file() {
+            fs::File::options()
+                .write(true)
+                .open(entry.path())
+                .unwrap()
+                .set_modified(when)
+                .unwrap();
+        }
+    }
+    fs::File::open(path).unwrap().set_modified(when).unwrap();
+}
+
+#[test]
+fn test_stale_ranks_and_archives_clean_projects() {
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+
+    let projects = env.path().join("1_Projects");
+    for name in ["old", "older", "fresh"] {
+        fs::create_dir_all(projects.join(name)).unwrap();
+        fs::write(projects.join(name).join("notes.md"), "todo\n").unwrap();
+    }
+    // Unsaved work in a repo keeps it out of bulk archiving, and so do
+    // commits that were never pushed
+    git2::Repository::init(projects.join("older")).unwrap();
+    fs::create_dir_all(projects.join("local")).unwrap();
+    fs::write(projects.join("local/notes.md"), "todo\n").unwrap();
+    let local = git2::Repository::init(projects.join("local")).unwrap();
+    let mut index = local.index().unwrap();
+    index.add_path(std::path::Path::new("notes.md")).unwrap();
+    let tree = local.find_tree(index.write_tree().unwrap()).unwrap();
+    let long_ago = chrono::Utc::now().timestamp() - 250 * 86400;
+    let sig =
+        git2::Signature::new("Test", "test@example.com", &git2::Time::new(long_ago, 0)).unwrap();
+    local
+        .commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+        .unwrap();
+    index.write().unwrap();
+    age_tree(&projects.join("old"), 200);
+    age_tree(&projects.join("older"), 400);
+    age_tree(&projects.join("local"), 250);
+
+    let output = env
+        .cmd()
+        .args(["stale", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stale: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    let names: Vec<&str> = stale.iter().map(|p| p["name"].as_str().unwrap()).collect();
+    assert_eq!(names, ["older", "local", "old"]);
+    assert_eq!(stale[0]["is_dirty"], true);
+    assert_eq!(stale[1]["is_dirty"], false);
+    assert_eq!(stale[1]["has_unpushed"], true);
+    assert!(stale[2]["idle_days"].as_i64().unwrap() >= 199);
+
+    env.cmd()
+        .args(["stale", "--days", "300", "--archive", "--yes"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Skipping 1 stale projects"));
+    assert!(projects.join("old").exists());
+
+    env.cmd()
+        .args(["stale", "--archive", "--yes"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Skipping 2 stale projects"));
+    assert!(env.path().join("4_Archives/old/notes.md").exists());
+    assert!(projects.join("older").exists());
+    assert!(projects.join("local").exists());
+    assert!(projects.join("fresh").exists());
+    assert!(projects.join("CTFs").exists());
+}
+
+#[test]
+fn test_info_reports_size_languages_git_para_and_ctf() {
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+
+    let info = |path: &std::path::Path| {
+        let output = env
+            .cmd()
+            .args(["info", "--format", "json"])
+            .arg(path)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()
+    };
+
+    let tool = env.path().join("1_Projects/tool");
+    fs::create_dir_all(tool.join("src")).unwrap();
+    fs::write(tool.join("src/main.rs"), "fn main() {}\n").unwrap();
+    fs::write(tool.join("src/lib.rs"), "pub fn f() {}\n").unwrap();
+    fs::write(tool.join("run.py"), "print(1)\n").unwrap();
+    fs::write(tool.join(".gitignore"), "dump.bin\n").unwrap();
+    fs::write(tool.join("dump.bin"), vec![0u8; 4096]).unwrap();
+    git2::Repository::init(&tool).unwrap();
+
+    let report = info(&tool);
+    assert_eq!(report["kind"], "directory");
+    assert_eq!(report["file_count"], 4);
+    assert!(report["size_bytes"].as_u64().unwrap() < 4096);
+    assert_eq!(report["languages"][0]["language"], "Rust");
+    assert_eq!(report["languages"][0]["files"], 2);
+    assert_eq!(report["para"]["area"], "projects");
+    assert_eq!(report["para"]["item"], "tool");
+    assert_eq!(report["git"]["is_dirty"], true);
+    assert!(report["ctf"].is_null());
+
+    fs::write(tool.join("logo.png"), b"\x89PNG\r\n\x1a\n0000").unwrap();
+    assert_eq!(info(&tool.join("logo.png"))["magic"], "image/png");
+
+    let event = env.path().join("1_Projects/CTFs/2025/Quals");
+    fs::create_dir_all(event.join("web/login")).unwrap();
+    fs::create_dir_all(event.join("web/upload")).unwrap();
+    fs::write(
+        event.join(".ctf_meta.json"),
+        r#"{"name": "Quals", "date": "2025-01-01", "year": 2025, "created_at": 0}"#,
+    )
+    .unwrap();
+    fs::write(event.join("web/login/flag.txt"), "CTF{ok}\n").unwrap();
+
+    let ctf = &info(&event)["ctf"];
+    assert_eq!(ctf["event"], "Quals");
+    assert_eq!(ctf["challenges"], 2);
+    assert_eq!(ctf["solved"], 1);
+    let ctf = &info(&event.join("web/login"))["ctf"];
+    assert_eq!(ctf["category"], "web");
+    assert_eq!(ctf["challenge"], "login");
+    assert_eq!(ctf["flag"], "CTF{ok}");
+
+    env.cmd()
+        .arg("info")
+        .arg(event.join("web"))
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("CTF:       Quals / web")
+                .and(predicate::str::contains("1/2 challenges")),
+        );
+
+    // A single document has no NDJSON or CSV form
+    env.cmd()
+        .args(["info", "--format", "ndjson"])
+        .arg(&event)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("possible values: human, json"));
+}
+
+#[test]
+fn test_status_details_filters_and_sort() {
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+
+    let busy = env.path().join("1_Projects/busy");
+    let tidy = env.path().join("1_Projects/tidy");
+    for dir in [&busy, &tidy] {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join("README.md"), "hello\n").unwrap();
+    }
+    commit_all(&git2::Repository::init(&tidy).unwrap(), "Tidy up");
+
+    let mut repo = git2::Repository::init(&busy).unwrap();
+    fs::write(busy.join("a.txt"), "a\n").unwrap();
+    commit_all(&repo, "Add files");
+    // One stash, one staged file, one modified file, one untracked file
+    fs::write(busy.join("a.txt"), "stashed\n").unwrap();
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    repo.stash_save(&sig, "wip", None).unwrap();
+    fs::write(busy.join("staged.txt"), "s\n").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(std::path::Path::new("staged.txt")).unwrap();
+    index.write().unwrap();
+    fs::write(busy.join("README.md"), "changed\n").unwrap();
+    fs::write(busy.join("new.txt"), "n\n").unwrap();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.branch("feature", &head, false).unwrap();
+    fs::write(busy.join(".git/MERGE_HEAD"), format!("{}\n", head.id())).unwrap();
+
+    env.cmd()
+        .args(["status", "--sort", "changes"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::is_match(
+                r"busy\s+(master|main)\s+\+1 ~1 \?1\s+Local\s+1\s+\d+m\s+Add files \(Test\)",
+            )
+            .unwrap()
+            .and(predicate::str::contains("merge in progress"))
+            .and(predicate::str::contains(
+                "2 local branches without upstream",
+            ))
+            .and(predicate::str::is_match(r"(?s)busy.*tidy").unwrap()),
+        );
+
+    env.cmd()
+        .args(["status", "--dirty"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("busy").and(predicate::str::contains("tidy").not()));
+}
+
+/// Run the git CLI in `dir` with a fixed identity
+fn git(dir: &std::path::Path, args: &[&str]) {
+    let status = std::process::Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .env("GIT_AUTHOR_NAME", "Test")
+        .env("GIT_AUTHOR_EMAIL", "test@example.com")
+        .env("GIT_COMMITTER_NAME", "Test")
+        .env("GIT_COMMITTER_EMAIL", "test@example.com")
+        .output()
+        .unwrap();
+    assert!(
+        status.status.success(),
+        "git {:?} failed: {:?}",
+        args,
+        status
+    );
+}
+
+#[test]
+fn test_git_bulk_fetch_pull_push_with_file_remote() {
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+
+    let remote_dir = TempDir::new().unwrap();
+    let remote = format!("file://{}/remote.git", remote_dir.path().display());
+    git(remote_dir.path(), &["init", "-q", "--bare", "remote.git"]);
+
+    let projects = env.path().join("1_Projects");
+    git(&projects, &["clone", "-q", &remote, "app"]);
+    let app = projects.join("app");
+    fs::write(app.join("one.txt"), "1\n").unwrap();
+    git(&app, &["add", "."]);
+    git(&app, &["commit", "-q", "-m", "one"]);
+    git(&app, &["push", "-q", "-u", "origin", "HEAD"]);
+    git(&projects, &["clone", "-q", &remote, "mirror"]);
+    let mirror = projects.join("mirror");
+
+    fs::write(app.join("two.txt"), "2\n").unwrap();
+    git(&app, &["add", "."]);
+    git(&app, &["commit", "-q", "-m", "two"]);
+
+    env.cmd()
+        .args(["git", "push", "--path", "*/app", "-j", "2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("app").and(predicate::str::contains("mirror").not()));
+
+    env.cmd()
+        .args(["git", "fetch"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"mirror\s+✓\s+↓ 1").unwrap());
+
+    env.cmd()
+        .args(["git", "pull", "--ff-only", "--behind"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"mirror\s+✓\s+Synced").unwrap());
+    assert!(mirror.join("two.txt").exists());
+
+    // Diverged history: the push is rejected and the command fails
+    fs::write(mirror.join("three.txt"), "3\n").unwrap();
+    git(&mirror, &["add", "."]);
+    git(&mirror, &["commit", "-q", "-m", "three"]);
+    fs::write(app.join("four.txt"), "4\n").unwrap();
+    git(&app, &["add", "."]);
+    git(&app, &["commit", "-q", "-m", "four"]);
+    git(&app, &["push", "-q"]);
+
+    env.cmd()
+        .args(["git", "push", "--path", "mirror"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::is_match(r"mirror\s+✗").unwrap())
+        .stderr(predicate::str::contains("1 of 1 repositories failed"));
+}
+
+#[test]
+fn test_status_discovers_worktrees_bare_repos_and_submodules() {
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+
+    let projects = env.path().join("1_Projects");
+    let init_commit = |dir: &std::path::Path| {
+        fs::create_dir_all(dir).unwrap();
+        git(dir, &["init", "-q"]);
+        fs::write(dir.join("README.md"), "x\n").unwrap();
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "-q", "-m", "init"]);
+    };
+
+    init_commit(&projects.join("main"));
+    // Nested inside another repo: not reported on its own
+    init_commit(&projects.join("main/inner"));
+    // Deeper than the default discovery depth of 3
+    init_commit(&projects.join("group/team/deep"));
+    git(
+        &projects.join("main"),
+        &["worktree", "add", "-q", "../main-wt"],
+    );
+    git(
+        &env.path().join("3_Resources"),
+        &["clone", "-q", "--bare", "../1_Projects/main", "mirror.git"],
+    );
+    let lib = TempDir::new().unwrap();
+    init_commit(lib.path());
+    git(
+        &projects.join("main"),
+        &[
+            "-c",
+            "protocol.file.allow=always",
+            "submodule",
+            "add",
+            "-q",
+            lib.path().to_str().unwrap(),
+            "libs/dep",
+        ],
+    );
+
+    let output = env.cmd().arg("status").output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("main-wt"), "{}", stdout);
+    assert!(stdout.contains("linked worktree"));
+    assert!(stdout.contains("mirror.git"));
+    assert!(stdout.contains("bare repository"));
+    assert!(!stdout.contains("inner"));
+    assert!(!stdout.contains("deep"));
+    assert!(!stdout.contains("↳ dep"));
+
+    env.cmd()
+        .args(["status", "--depth", "4", "--submodules"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("deep")
+                .and(predicate::str::is_match(r"(?m)^main\s.*\n(?:\s.*\n)*\s+↳ dep\s").unwrap())
+                .and(predicate::str::contains("inner").not()),
+        );
+}
+
+#[test]
+fn test_status_cache_reused_until_repo_changes() {
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+
+    let app = env.path().join("1_Projects/app");
+    fs::create_dir_all(app.join("src")).unwrap();
+    fs::write(app.join("README.md"), "x\n").unwrap();
+    fs::write(app.join("src/main.rs"), "fn main() {}\n").unwrap();
+    commit_all(&git2::Repository::init(&app).unwrap(), "init");
+    fs::write(app.join("todo.txt"), "x\n").unwrap();
+
+    let status = |args: &[&str], changes: &str, cached: usize| {
+        env.cmd()
+            .arg("status")
+            .args(args)
+            .assert()
+            .success()
+            .stdout(
+                predicate::str::is_match(format!(r"app\s+\S+\s+{}\s", regex::escape(changes)))
+                    .unwrap(),
+            )
+            .stderr(predicate::str::contains(format!("{} from cache", cached)));
+    };
+
+    status(&[], "?1", 0);
+    assert!(fs::read_dir(env.path().join(".cache/wardex"))
+        .unwrap()
+        .flatten()
+        .any(|e| e.file_name().to_string_lossy().starts_with("status-")));
+    status(&[], "?1", 1);
+
+    // A new top-level file changes the work tree mtime
+    fs::write(app.join("more.txt"), "x\n").unwrap();
+    status(&[], "?2", 0);
+    status(&[], "?2", 1);
+    status(&["--refresh"], "?2", 0);
+
+    // Committing moves HEAD and rewrites the index
+    commit_all(&git2::Repository::open(&app).unwrap(), "more");
+    status(&[], "✓ Clean", 0);
+    status(&[], "✓ Clean", 1);
+
+    // Editing tracked files, including ones in subdirectories
+    fs::write(app.join("src/main.rs"), "fn main() { todo!() }\n").unwrap();
+    fs::write(app.join("README.md"), "changed\n").unwrap();
+    status(&[], "~2", 0);
+    env.cmd()
+        .args(["status", "--check"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("uncommitted changes (~2)"));
+
+    // A new file next to tracked ones in a subdirectory
+    fs::write(app.join("src/extra.rs"), "\n").unwrap();
+    status(&[], "~2 ?1", 0);
+}
+
+#[test]
+fn test_status_json_and_check_exit_code() {
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+
+    let remote_dir = TempDir::new().unwrap();
+    let remote = format!("file://{}/remote.git", remote_dir.path().display());
+    git(remote_dir.path(), &["init", "-q", "--bare", "remote.git"]);
+
+    let projects = env.path().join("1_Projects");
+    git(&projects, &["clone", "-q", &remote, "app"]);
+    let app = projects.join("app");
+    fs::write(app.join("one.txt"), "1\n").unwrap();
+    git(&app, &["add", "."]);
+    git(&app, &["commit", "-q", "-m", "one"]);
+    git(&app, &["push", "-q", "-u", "origin", "HEAD"]);
+
+    env.cmd()
+        .args(["status", "--check"])
+        .assert()
+        .success()
+        .stdout("")
+        .stderr(predicate::str::contains(
+            "All 1 repos are committed and pushed",
+        ));
+
+    // app ends up diverged, mirror dirty
+    git(&projects, &["clone", "-q", &remote, "mirror"]);
+    let mirror = projects.join("mirror");
+    fs::write(mirror.join("two.txt"), "2\n").unwrap();
+    git(&mirror, &["add", "."]);
+    git(&mirror, &["commit", "-q", "-m", "two"]);
+    git(&mirror, &["push", "-q"]);
+    fs::write(mirror.join("scratch.txt"), "x\n").unwrap();
+    fs::write(app.join("three.txt"), "3\n").unwrap();
+    git(&app, &["add", "."]);
+    git(&app, &["commit", "-q", "-m", "three"]);
+    git(&app, &["fetch", "-q"]);
+
+    env.cmd()
+        .args(["status", "--check"])
+        .assert()
+        .failure()
+        .stdout(
+            predicate::str::contains("app: diverged from upstream (1 ahead, 1 behind)")
+                .and(predicate::str::contains("mirror: uncommitted changes (?1)")),
+        )
+        .stderr(predicate::str::contains("2 of 2 repos need attention"));
+
+    let output = env
+        .cmd()
+        .args(["status", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let repos = report["repos"].as_array().unwrap();
+    assert_eq!(repos.len(), 2);
+    assert_eq!(repos[0]["name"], "app");
+    assert_eq!(
+        repos[0]["sync_status"],
+        serde_json::json!({"diverged": {"ahead": 1, "behind": 1}})
+    );
+    assert_eq!(repos[0]["last_commit"]["summary"], "three");
+    assert_eq!(repos[1]["untracked"], 1);
+    assert_eq!(repos[1]["sync_status"], "synced");
+    assert_eq!(repos[1]["kind"], "normal");
+
+    // A repo that was never pushed fails the check just like --unpushed lists it
+    let solo = projects.join("solo");
+    fs::create_dir_all(&solo).unwrap();
+    fs::write(solo.join("notes.md"), "x\n").unwrap();
+    commit_all(&git2::Repository::init(&solo).unwrap(), "init");
+    env.cmd()
+        .args(["status", "--check"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "solo: 1 branches without upstream",
+        ))
+        .stderr(predicate::str::contains("3 of 3 repos need attention"));
+    env.cmd()
+        .args(["status", "--unpushed"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("solo"));
+
+    let output = env
+        .cmd()
+        .args(["status", "--format", "ndjson"])
+        .output()
+        .unwrap();
+    let lines: Vec<serde_json::Value> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[2]["name"], "solo");
+    assert_eq!(lines[2]["sync_status"], "local");
+
+    env.cmd()
+        .args(["status", "--format", "csv"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::starts_with("name,path,kind,branch,sync,")
+                .and(predicate::str::contains("mirror,"))
+                .and(predicate::str::contains("uncommitted changes (?1)")),
+        );
+}
+
+#[test]
+fn test_stats_record_and_history() {
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+
+    env.cmd()
+        .args(["stats", "history"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("No snapshots yet"));
+
+    let app = env.path().join("1_Projects/app");
+    fs::create_dir_all(&app).unwrap();
+    fs::write(app.join("a.bin"), vec![0u8; 1000]).unwrap();
+    fs::write(env.path().join("3_Resources/notes.md"), "# notes\n").unwrap();
+    env.cmd().args(["stats", "--record"]).assert().success();
+
+    fs::write(app.join("b.bin"), vec![0u8; 3000]).unwrap();
+    let tool = env.path().join("1_Projects/tool");
+    fs::create_dir_all(&tool).unwrap();
+    fs::write(tool.join("main.rs"), "fn main() {}\n").unwrap();
+    env.cmd().args(["stats", "--record"]).assert().success();
+
+    env.cmd()
+        .args(["stats", "--record", "--tag", "x"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("drop the filters"));
+
+    let output = env
+        .cmd()
+        .args(["stats", "history", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let history: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let snapshots = history.as_array().unwrap();
+    assert_eq!(snapshots.len(), 2);
+    assert_eq!(snapshots[0]["project_sizes"]["app"], 1000);
+    assert_eq!(snapshots[1]["project_sizes"]["app"], 4000);
+    assert_eq!(snapshots[1]["project_sizes"]["tool"], 13);
+    assert_eq!(snapshots[1]["area_sizes"]["projects"], 4013);
+    assert_eq!(snapshots[1]["area_sizes"]["resources"], 8);
+    assert_eq!(snapshots[1]["file_types"]["bin"], 2);
+
+    env.cmd()
+        .args(["stats", "history"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Project growth since")
+                .and(predicate::str::is_match(r"app\s+1000 B →\s+3.9 KB\s+\(\+2.9 KB\)").unwrap()),
+        );
+
+    env.cmd()
+        .args(["stats", "history", "--project", "app", "--format", "csv"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("timestamp,size_bytes").and(predicate::str::contains(",4000")),
+        );
+
+    env.cmd()
+        .args(["stats", "history", "-n", "1", "--format", "ndjson"])
+        .assert()
+        .success()
+        .stdout(predicate::function(|out: &str| out.lines().count() == 1));
+}
+
+#[test]
+fn test_stats_size_breakdown_and_tree() {
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+
+    let app = env.path().join("1_Projects/app");
+    fs::create_dir_all(app.join("src")).unwrap();
+    fs::create_dir_all(app.join("target/debug")).unwrap();
+    fs::write(app.join(".gitignore"), "target/\n").unwrap();
+    fs::write(app.join("src/main.rs"), vec![b'x'; 2048]).unwrap();
+    fs::write(app.join("target/debug/app"), vec![0u8; 50_000]).unwrap();
+    let tool = env.path().join("1_Projects/tool");
+    fs::create_dir_all(&tool).unwrap();
+    fs::write(tool.join("data.bin"), vec![0u8; 10_000]).unwrap();
+    fs::write(env.path().join("3_Resources/notes.md"), "notes\n").unwrap();
+    // Only honoured inside a repository
+    git(&app, &["init", "-q"]);
+
+    env.cmd().arg("stats").assert().success().stdout(
+        predicate::str::contains("Size by Area")
+            .and(predicate::str::is_match(r"projects\s+11.8 KB").unwrap())
+            .and(predicate::str::is_match(r"tool\s+9.8 KB").unwrap())
+            .and(predicate::str::contains("1_Projects/tool/data.bin"))
+            .and(predicate::str::contains("target/debug/app").not()),
+    );
+
+    env.cmd()
+        .args(["stats", "--include-ignored", "--top", "1"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::is_match(r"app\s+50.8 KB")
+                .unwrap()
+                .and(predicate::str::contains("1_Projects/app/target/debug/app"))
+                .and(predicate::str::contains("tool").not()),
+        );
+
+    env.cmd()
+        .args(["stats", "tree", "-d", "3"])
+        .current_dir(env.path())
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::is_match(r"11.8 KB  ├── 1_Projects/")
+                .unwrap()
+                .and(predicate::str::is_match(r"├── app/\n\s+2.0 KB  │   │   └── src/").unwrap()),
+        );
+
+    let output = env
+        .cmd()
+        .args([
+            "stats",
+            "tree",
+            "--include-ignored",
+            "--top",
+            "1",
+            "--format",
+            "json",
+        ])
+        .arg(env.path().join("1_Projects"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["tree"]["size_bytes"], 62_048);
+    assert_eq!(report["tree"]["children"][0]["name"], "app");
+    assert_eq!(
+        report["tree"]["children"][0]["children"][0]["name"],
+        "target"
+    );
+    assert_eq!(report["tree"]["hidden_children"], 2);
+    assert_eq!(report["tree"]["hidden_bytes"], 10_000);
+    assert_eq!(report["largest_files"][0]["size_bytes"], 50_000);
+
+    env.cmd()
+        .args(["stats", "tree", "--include-ignored", "--format", "ndjson"])
+        .arg(env.path().join("1_Projects"))
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::is_match(r#"^\{"path":"[^"]*app","size_bytes":50000\}\n"#)
+                .unwrap()
+                .and(predicate::str::contains("children").not()),
+        );
+}
+
+#[test]
+fn test_stats_counts_ctf_events_challenges_and_solves() {
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+
+    let ctfs = env.path().join("1_Projects/CTFs");
+    for chal in ["live/web/login", "live/pwn/heap", "2023/old/crypto/rsa"] {
+        fs::create_dir_all(ctfs.join(chal)).unwrap();
+    }
+    fs::write(ctfs.join("live/web/login/flag.txt"), "flag{a}\n").unwrap();
+    fs::write(ctfs.join("2023/old/crypto/rsa/flag.txt"), "flag{b}\n").unwrap();
+
+    env.cmd()
+        .arg("stats")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "CTF Events:  2 (2/3 challenges solved)",
+        ));
+
+    env.cmd()
+        .args(["ctf", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"live\s+0\s+-\s+2\s+1").unwrap());
+
+    // A real solve moves the challenge of an initialized event into the archives
+    env.cmd()
+        .args(["ctf", "init", "Solvable", "--date", "2024-05-01"])
+        .assert()
+        .success();
+    let event = fs::read_dir(&ctfs)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .find(|p| p.join(".ctf_meta.json").exists())
+        .unwrap();
+    fs::create_dir_all(event.join("web/xss")).unwrap();
+    fs::create_dir_all(event.join("crypto/aes")).unwrap();
+    git(&event, &["init", "-q"]);
+    env.cmd()
+        .args(["ctf", "solve", "flag{solved}", "--force"])
+        .current_dir(event.join("web/xss"))
+        .assert()
+        .success();
+    assert!(!event.join("web/xss").exists());
+    assert!(env
+        .path()
+        .join("4_Archives/CTFs")
+        .join(event.file_name().unwrap())
+        .join("web/xss/flag.txt")
+        .is_file());
+
+    env.cmd()
+        .arg("stats")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "CTF Events:  3 (3/5 challenges solved)",
+        ));
+
+    env.cmd()
+        .args(["ctf", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"Solvable\s+2024\s+2024-05-01\s+2\s+1").unwrap());
+}
+
+#[test]
+fn test_reclaim_finds_and_trashes_idle_artifacts() {
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+
+    let projects = env.path().join("1_Projects");
+    let crate_dir = projects.join("oldcrate");
+    fs::create_dir_all(crate_dir.join("target/debug")).unwrap();
+    fs::write(crate_dir.join("Cargo.toml"), "[package]\n").unwrap();
+    fs::write(crate_dir.join("target/debug/app"), vec![0u8; 40_000]).unwrap();
+
+    let py = env.path().join("2_Areas/scripts");
+    fs::create_dir_all(py.join(".venv/lib")).unwrap();
+    fs::create_dir_all(py.join("pkg/__pycache__")).unwrap();
+    fs::create_dir_all(py.join("__pycache__")).unwrap();
+    fs::write(py.join("pyproject.toml"), "[project]\n").unwrap();
+    fs::write(py.join(".venv/lib/site.py"), vec![b'x'; 5_000]).unwrap();
+    fs::write(py.join("pkg/__pycache__/a.pyc"), vec![0u8; 300]).unwrap();
+    fs::write(py.join("__pycache__/b.pyc"), vec![0u8; 200]).unwrap();
+
+    let web = projects.join("web");
+    fs::create_dir_all(web.join("node_modules/left-pad")).unwrap();
+    fs::write(web.join("package.json"), "{}\n").unwrap();
+    fs::write(
+        web.join("node_modules/left-pad/index.js"),
+        vec![b'x'; 20_000],
+    )
+    .unwrap();
+
+    age_tree(&crate_dir, 100);
+    age_tree(&py, 60);
+
+    let output = env
+        .cmd()
+        .args(["reclaim", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let artifacts: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    let summary: Vec<(&str, &str, u64)> = artifacts
+        .iter()
+        .map(|a| {
+            (
+                a["project"].as_str().unwrap(),
+                a["name"].as_str().unwrap(),
+                a["size_bytes"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("1_Projects/oldcrate", "target", 40_000),
+            ("1_Projects/web", "node_modules", 20_000),
+            ("2_Areas/scripts", ".venv", 5_000),
+            ("2_Areas/scripts", "__pycache__", 500),
+        ]
+    );
+    assert_eq!(artifacts[0]["kind"], "rust");
+    assert!(artifacts[0]["project_idle_days"].as_i64().unwrap() >= 99);
+    assert_eq!(artifacts[1]["project_idle_days"], 0);
+    assert_eq!(artifacts[3]["paths"].as_array().unwrap().len(), 2);
+
+    let output = env
+        .cmd()
+        .args(["reclaim", "--format", "ndjson"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let lines: Vec<serde_json::Value> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(lines, artifacts);
+
+    let output = env
+        .cmd()
+        .args(["reclaim", "--format", "csv"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let csv = String::from_utf8(output.stdout).unwrap();
+    let rows: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        rows[0],
+        "project,kind,name,paths,size_bytes,last_used,project_idle_days"
+    );
+    assert_eq!(rows.len(), 5);
+    assert!(rows[1].starts_with("1_Projects/oldcrate,rust,target,"));
+    assert!(rows[4].starts_with("2_Areas/scripts,python,__pycache__,"));
+    assert_eq!(rows[4].matches("__pycache__").count(), 3);
+    assert!(rows[4].contains(",500,"));
+
+    env.cmd()
+        .args(["reclaim", "--days", "30", "--clean", "--dry-run"])
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("Would trash")
+                .and(predicate::str::contains("node_modules").not()),
+        );
+    assert!(crate_dir.join("target").exists());
+
+    env.cmd()
+        .args(["reclaim", "--days", "30", "--clean", "--yes"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Reclaimed 44.4 KB"));
+    assert!(!crate_dir.join("target").exists());
+    assert!(!py.join(".venv").exists());
+    assert!(!py.join("pkg/__pycache__").exists());
+    assert!(web.join("node_modules").exists());
+    assert!(crate_dir.join("Cargo.toml").exists());
+
+    let trash = env.path().join("Trash");
+    assert!(trash.join("files/target/debug/app").exists());
+    let info = fs::read_to_string(trash.join("info/__pycache__.trashinfo")).unwrap();
+    assert!(info.contains("Path=") && info.contains("scripts/"));
+    assert!(trash.join("files/__pycache__.1").exists());
+
+    env.cmd()
+        .args(["reclaim", "--days", "0", "--clean", "--delete", "--yes"])
+        .assert()
+        .success();
+    assert!(!web.join("node_modules").exists());
+    assert!(!trash.join("files/node_modules").exists());
+}
+
+#[test]
+fn test_audit_checks_selection_and_ignore_globs() {
+    use std::io::Write;
+
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+
+    let res = env.path().join("3_Resources");
+    fs::write(res.join("photo.txt"), b"\x89PNG\r\n\x1a\n0000").unwrap();
+    fs::create_dir_all(res.join("drafts/empty")).unwrap();
+    fs::write(res.join("drafts/logo.txt"), b"\x89PNG\r\n\x1a\n0000").unwrap();
+
+    env.cmd()
+        .args(["audit", "--list-checks"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("empty-dirs").and(predicate::str::contains("ext-mismatch")),
+        );
+
+    let findings = |args: &[&str]| -> Vec<serde_json::Value> {
+        let output = env
+            .cmd()
+            .arg("audit")
+            .args(args)
+            .args(["--format", "json"])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        serde_json::from_slice(&output.stdout).unwrap()
+    };
+    let rel = |f: &serde_json::Value| {
+        std::path::Path::new(f["path"].as_str().unwrap())
+            .strip_prefix(env.path())
+            .unwrap()
+            .display()
+            .to_string()
+    };
+
+    let all = findings(&[]);
+    assert!(all.iter().any(|f| f["check"] == "empty-dirs"
+        && rel(f) == "3_Resources/drafts/empty"
+        && f["severity"] == "info"));
+
+    let mismatched = findings(&["--check", "ext-mismatch"]);
+    let paths: Vec<String> = mismatched.iter().map(rel).collect();
+    assert_eq!(
+        paths,
+        ["3_Resources/drafts/logo.txt", "3_Resources/photo.txt"]
+    );
+    assert_eq!(mismatched[0]["message"], "named .txt, content is .png");
+    assert_eq!(mismatched[0]["severity"], "warning");
+
+    let mut config = fs::OpenOptions::new()
+        .append(true)
+        .open(env.path().join("config.yaml"))
+        .unwrap();
+    writeln!(
+        config,
+        "\naudit:\n  enabled: [ext-mismatch]\n  ignore: ['3_Resources/drafts']"
+    )
+    .unwrap();
+
+    let configured = findings(&[]);
+    assert!(configured.iter().all(|f| f["check"] != "empty-dirs"));
+    assert!(configured.iter().all(|f| !rel(f).contains("drafts")));
+    assert!(configured.iter().any(|f| rel(f) == "3_Resources/photo.txt"));
+
+    env.cmd()
+        .args(["audit", "--check", "empty-dirs,nope"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown audit check 'nope'"));
+}
+
+#[test]
+fn test_audit_fix_dry_run_apply_and_undo() {
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+
+    let res = env.path().join("3_Resources");
+    fs::create_dir_all(res.join("old/a/b")).unwrap();
+    fs::write(res.join("photo.txt"), b"\x89PNG\r\n\x1a\n0000").unwrap();
+    // Renaming would clobber logo.png
+    fs::write(res.join("logo.txt"), b"\x89PNG\r\n\x1a\n0000").unwrap();
+    fs::write(res.join("logo.png"), b"keep").unwrap();
+    // Container formats are fine as they are
+    let zip = b"PK\x03\x04\x14\x00\x00\x00\x08\x00";
+    fs::write(res.join("app.jar"), zip).unwrap();
+    fs::write(res.join("pkg-1.0-py3-none-any.whl"), zip).unwrap();
+    fs::write(res.join("bundle.tgz"), b"\x1f\x8b\x08\x00\x00\x00\x00\x00").unwrap();
+    // A different known format is reported but left alone
+    fs::write(res.join("backup.tar"), b"\x1f\x8b\x08\x00\x00\x00\x00\x00").unwrap();
+
+    env.cmd()
+        .args(["audit", "--check", "ext-mismatch"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("named .tar, content is .gz")
+                .and(predicate::str::contains("app.jar").not())
+                .and(predicate::str::contains(".whl").not())
+                .and(predicate::str::contains("bundle.tgz").not()),
+        );
+
+    // The clobbering rename is reported as failing even in a dry run
+    env.cmd()
+        .args(["audit", "--fix", "--dry-run"])
+        .assert()
+        .failure()
+        .stderr(
+            predicate::str::contains("Would remove empty folder")
+                .and(predicate::str::contains("3_Resources/old\n"))
+                .and(predicate::str::contains("Would rename"))
+                .and(predicate::str::contains("photo.png"))
+                .and(predicate::str::contains("backup.").not())
+                .and(predicate::str::contains("app.").not())
+                .and(predicate::str::contains("bundle.").not()),
+        );
+    assert!(res.join("old/a/b").exists());
+    assert!(res.join("photo.txt").exists());
+
+    env.cmd()
+        .args(["audit", "--fix", "--yes"])
+        .assert()
+        .failure()
+        .stderr(
+            predicate::str::contains("logo.png already exists")
+                .and(predicate::str::contains("Revert with `wardex undo -c 4`"))
+                .and(predicate::str::contains("1 of 5 fixes failed")),
+        );
+    assert!(!res.join("old").exists());
+    assert!(res.join("photo.png").exists());
+    assert_eq!(fs::read(res.join("logo.png")).unwrap(), b"keep");
+    assert!(res.join("backup.tar").exists());
+    // Configured folders are never removed, even when empty
+    for folder in ["0_Inbox", "2_Areas", "4_Archives", "1_Projects/CTFs"] {
+        assert!(env.path().join(folder).is_dir(), "{} removed", folder);
+    }
+
+    env.cmd().args(["undo", "-c", "4"]).assert().success();
+    assert!(res.join("old/a/b").is_dir());
+    assert!(res.join("photo.txt").exists());
+}
+
+#[test]
+fn test_ctf_flag_format_used_by_search_and_solve() {
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+
+    env.cmd()
+        .args(["ctf", "init", "HtbEvent", "--flag-format", "HTB{...}"])
+        .assert()
+        .success();
+
+    let ctf_root = env.path().join("1_Projects/CTFs");
+    let event_dir = fs::read_dir(&ctf_root)
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .find(|e| e.file_name().to_string_lossy().contains("HtbEvent"))
+        .unwrap()
+        .path();
+
+    let meta = fs::read_to_string(event_dir.join(".ctf_meta.json")).unwrap();
+    assert!(meta.contains("\"HTB\""));
+
+    let chall = event_dir.join("web/login");
+    fs::create_dir_all(&chall).unwrap();
+    fs::write(chall.join("dump.txt"), "HTB{real_one}\nflag{decoy}\n").unwrap();
+
+    // Inside the event, the event format replaces the default pattern
+    let mut cmd = env.cmd();
+    cmd.current_dir(&chall);
+    cmd.args(["search", "."])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("HTB{real_one}"))
+        .stdout(predicate::str::contains("flag{decoy}").not());
+
+    // Solve rejects a flag in the wrong format
+    let mut cmd = env.cmd();
+    cmd.current_dir(&chall);
+    cmd.args(["ctf", "solve", "flag{decoy}"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("does not match"));
+    assert!(!chall.join("flag.txt").exists());
+}
+
+#[test]
+fn test_search_archive_entries_streamed_with_limits() {
+    use std::io::Write;
+
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+    let mut config = fs::OpenOptions::new()
+        .append(true)
+        .open(env.path().join("config.yaml"))
+        .unwrap();
+    writeln!(
+        config,
+        "\nsearch:\n  max_archive_entry_size_mb: 4\n  buffer_limit_mb: 1"
+    )
+    .unwrap();
+
+    let chall = env.path().join("1_Projects/chall");
+    fs::create_dir_all(&chall).unwrap();
+
+    let zip_file = fs::File::create(chall.join("bundle.zip")).unwrap();
+    let mut zip = zip::ZipWriter::new(zip_file);
+    let options = zip::write::SimpleFileOptions::default();
+    zip.start_file("notes.txt", options).unwrap();
+    zip.write_all(b"line one\nbinary\x00junk flag{in_zip} more\n")
+        .unwrap();
+    zip.start_file("huge.bin", options).unwrap();
+    zip.write_all(&vec![b'A'; 5 * 1024 * 1024]).unwrap();
+    zip.start_file("oneline.txt", options).unwrap();
+    zip.write_all(&vec![b'B'; 2 * 1024 * 1024]).unwrap();
+    zip.finish().unwrap();
+
+    let output = env
+        .cmd()
+        .args(["search", chall.to_str().unwrap(), "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let m = &report["matches"][0];
+    assert_eq!(m["matched_text"], "flag{in_zip}");
+    assert_eq!(m["archive_entry"], "notes.txt");
+
+    let skipped = report["skipped"].as_array().unwrap();
+    assert_eq!(skipped.len(), 2);
+    assert_eq!(skipped[0]["archive_entry"], "huge.bin");
+    assert!(skipped[0]["reason"]
+        .as_str()
+        .unwrap()
+        .contains("exceeds limit"));
+    assert_eq!(skipped[1]["archive_entry"], "oneline.txt");
+    assert_eq!(
+        skipped[1]["reason"],
+        "line longer than buffer limit of 1 MB"
+    );
+
+    // Streamed matches end with a summary line carrying the skipped entries
+    let output = env
+        .cmd()
+        .args(["search", chall.to_str().unwrap(), "--format", "ndjson"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let lines: Vec<serde_json::Value> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["matched_text"], "flag{in_zip}");
+    let summary = &lines[1]["summary"];
+    assert_eq!(summary["skipped"].as_array().unwrap().len(), 2);
+    assert_eq!(summary["skipped"][1]["archive_entry"], "oneline.txt");
+    assert!(summary["errors"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_search_strings_mode_finds_flags_in_binaries() {
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+
+    let chall = env.path().join("1_Projects/rev");
+    fs::create_dir_all(&chall).unwrap();
+
+    // ELF-ish blob: NUL early, an ASCII flag at offset 16, and a UTF-16LE flag after it
+    let mut blob = b"\x7fELF\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00".to_vec();
+    blob.extend_from_slice(b"flag{ascii_in_elf}");
+    blob.extend_from_slice(&[0u8; 3]);
+    let utf16_offset = blob.len();
+    for c in "CTF{wide_one}".bytes() {
+        blob.push(c);
+        blob.push(0);
+    }
+    blob.extend_from_slice(&[0xffu8; 8]);
+    fs::write(chall.join("chall.bin"), &blob).unwrap();
+
+    // Default mode stops at the first NUL
+    env.cmd()
+        .args(["search", chall.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("flag{ascii_in_elf}").not());
+
+    let output = env
+        .cmd()
+        .args([
+            "search",
+            chall.to_str().unwrap(),
+            "--strings",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let mut matches: Vec<(String, u64)> = report["matches"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| {
+            (
+                m["matched_text"].as_str().unwrap().to_string(),
+                m["byte_offset"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    matches.sort();
+    assert_eq!(
+        matches,
+        vec![
+            ("CTF{wide_one}".to_string(), utf16_offset as u64),
+            ("flag{ascii_in_elf}".to_string(), 16),
+        ]
+    );
+
+    // Text whose first NUL comes after the sniffed prefix is still scanned past it
+    let mut late_nul = "padding line\n".repeat(1024).into_bytes();
+    late_nul.extend_from_slice(b"\x00\x01\x02junk\nflag{after_late_nul}\n");
+    fs::remove_file(chall.join("chall.bin")).unwrap();
+    fs::write(chall.join("log.txt"), &late_nul).unwrap();
+
+    env.cmd()
+        .args(["search", chall.to_str().unwrap(), "--strings"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("flag{after_late_nul}"));
+}
+
+/// Build an Ethernet/IPv4/TCP frame (checksums are not validated by the parser)
+fn tcp_frame(src_port: u16, dst_port: u16, seq: u32, flags: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = vec![0u8; 12];
+    frame.extend_from_slice(&[0x08, 0x00]);
+
+    let total_len = (20 + 20 + payload.len()) as u16;
+    frame.extend_from_slice(&[0x45, 0]);
+    frame.extend_from_slice(&total_len.to_be_bytes());
+    frame.extend_from_slice(&[0, 0, 0, 0, 64, 6, 0, 0]);
+    frame.extend_from_slice(&[10, 0, 0, 1]);
+    frame.extend_from_slice(&[10, 0, 0, 2]);
+
+    frame.extend_from_slice(&src_port.to_be_bytes());
+    frame.extend_from_slice(&dst_port.to_be_bytes());
+    frame.extend_from_slice(&seq.to_be_bytes());
+    frame.extend_from_slice(&[0, 0, 0, 0, 0x50, flags, 0xff, 0xff, 0, 0, 0, 0]);
+    frame.extend_from_slice(payload);
+    frame
+}
+
+#[test]
+fn test_search_pcap_reassembles_http_gzip_body() {
+    use flate2::write::GzEncoder;
+    use std::io::Write;
+
+    let env = TestEnv::new();
+    env.setup_workspace();
+    env.create_config();
+
+    let mut gz = GzEncoder::new(Vec::new(), flate2::Compression::default());
+    gz.write_all(b"<html>secret: flag{gzipped_http}</html>")
+        .unwrap();
+    let body = gz.finish().unwrap();
+
+    let mut response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Encoding: gzip\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n",
+        body.len()
+    )
+    .into_bytes();
+    response.extend_from_slice(&body);
+    response.extend_from_slice(b"\r\n0\r\n\r\n");
+    let (first, second) = response.split_at(response.len() / 2);
+
+    // SYN, then the two halves of the response captured out of order
+    let isn = 1000u32;
+    let frames = [
+        tcp_frame(80, 51000, isn, 0x12, b""),
+        tcp_frame(80, 51000, isn + 1 + first.len() as u32, 0x18, second),
+        tcp_frame(80, 51000, isn + 1, 0x18, first),
+    ];
+
+    let mut pcap = Vec::new();
+    pcap.extend_from_slice(&0xa1b2c3d4u32.to_le_bytes());
+    pcap.extend_from_slice(&[2, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    pcap.extend_from_slice(&65535u32.to_le_bytes());
+    pcap.extend_from_slice(&1u32.to_le_bytes());
+    for frame in &frames {
+        pcap.extend_from_slice(&[0u8; 8]);
+        pcap.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+        pcap.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+        pcap.extend_from_slice(frame);
+    }
+
+    let dir = env.path().join("1_Projects/forensics");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("capture.pcap"), &pcap).unwrap();
+
+    env.cmd()
+        .args(["search", dir.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("flag{gzipped_http}"))
+        .stdout(predicate::str::contains("http body #1"));
+}